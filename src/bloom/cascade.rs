@@ -0,0 +1,231 @@
+use crate::bloom::utils::{BloomError, BloomFilter};
+use crate::configs::{BLOOM_FP_RATE_MAX, BLOOM_FP_RATE_MIN};
+use serde::{Deserialize, Serialize};
+
+/// Version tag prefixed to every encoded `BloomCascade`, mirroring `BloomObject::encode_object`'s
+/// versioning scheme so future on-disk/wire format changes can be introduced without breaking
+/// existing replicas. See `BLOOM_CASCADE_VERSION`.
+pub const BLOOM_CASCADE_VERSION: u8 = 1;
+
+// A cascade is capped at this many levels as a sanity backstop against pathological inputs (e.g.
+// R and S overlapping) that would otherwise alternate indefinitely without the residue ever emptying.
+const MAX_CASCADE_LEVELS: usize = 64;
+
+/// A multi-level bloom filter cascade providing EXACT (false-positive-free) membership testing over two
+/// known, disjoint sets: an "included" set R and an "excluded" set S, as used by Mozilla's CRLite /
+/// rust_cascade to encode certificate revocation compactly.
+///
+/// Level 0 encodes R. Querying an item not in R's level-0 filter proves it is not in R. Every subsequent
+/// level encodes the prior level's false-positive residue against the opposite set, alternating R/S, until
+/// a level would have no residue to encode - at that point every item in R ∪ S is classified with zero
+/// false positives, at the cost of the (geometrically shrinking) extra levels.
+#[derive(Serialize, Deserialize)]
+pub struct BloomCascade {
+    levels: Vec<BloomFilter>,
+    fp_rate: f64,
+}
+
+impl BloomCascade {
+    /// Builds a cascade encoding exact membership in `included` (R) against the disjoint `excluded` (S),
+    /// drawing a fresh OS-random seed for each level. Returns the cascade alongside the seed actually
+    /// used for each level (in build order) so a caller that must replicate this deterministically (see
+    /// `command_handler::bloom_cascade_build`) can hand those exact seeds to `build_with_seeds` on the
+    /// replica, rather than letting it draw its own randomness and silently diverge from the primary.
+    pub fn build(included: &[Vec<u8>], excluded: &[Vec<u8>], fp_rate: f64) -> (BloomCascade, Vec<[u8; 32]>) {
+        Self::build_levels(included, excluded, fp_rate, None)
+            .expect("building with freshly-drawn random seeds never runs out of seeds")
+    }
+
+    /// Deterministic counterpart to `build`: rebuilds a cascade using the exact per-level seeds a prior
+    /// `build` call returned, so a replica (or AOF replay) reconstructs the bit-identical cascade the
+    /// primary built instead of drawing its own random seeds. Returns
+    /// `BloomError::DecodeBloomFilterFailed` if `seeds` doesn't have at least one entry per level the
+    /// same inputs would produce - that means the replicated command and the primary's build have
+    /// diverged.
+    pub fn build_with_seeds(
+        included: &[Vec<u8>],
+        excluded: &[Vec<u8>],
+        fp_rate: f64,
+        seeds: &[[u8; 32]],
+    ) -> Result<BloomCascade, BloomError> {
+        Self::build_levels(included, excluded, fp_rate, Some(seeds)).map(|(cascade, _)| cascade)
+    }
+
+    /// Shared implementation behind `build`/`build_with_seeds`. When `seeds` is `None`, a fresh random
+    /// seed is drawn for each level; when `Some`, level `i` uses `seeds[i]` instead, failing if `seeds`
+    /// runs out before the cascade does.
+    fn build_levels(
+        included: &[Vec<u8>],
+        excluded: &[Vec<u8>],
+        fp_rate: f64,
+        seeds: Option<&[[u8; 32]]>,
+    ) -> Result<(BloomCascade, Vec<[u8; 32]>), BloomError> {
+        let mut levels: Vec<BloomFilter> = Vec::new();
+        let mut seeds_used: Vec<[u8; 32]> = Vec::new();
+        // `to_encode` is this level's input set; `parity` tracks whether `to_encode` is drawn from R
+        // (even levels) or S (odd levels), so we know which full original set to probe for the next
+        // level's residue.
+        let mut to_encode: Vec<&[u8]> = included.iter().map(|v| v.as_slice()).collect();
+        let mut parity_is_included = true;
+        while !to_encode.is_empty() && levels.len() < MAX_CASCADE_LEVELS {
+            let capacity = to_encode.len().max(1) as i64;
+            let mut level = match seeds {
+                Some(seeds) => {
+                    let seed = seeds
+                        .get(levels.len())
+                        .ok_or(BloomError::DecodeBloomFilterFailed)?;
+                    BloomFilter::with_fixed_seed(fp_rate, capacity, seed)
+                }
+                None => BloomFilter::with_random_seed(fp_rate, capacity),
+            };
+            let seed = level.seed();
+            for item in &to_encode {
+                level.set(item);
+            }
+            let opposite_full: &[Vec<u8>] = if parity_is_included {
+                excluded
+            } else {
+                included
+            };
+            let residue: Vec<&[u8]> = opposite_full
+                .iter()
+                .map(|v| v.as_slice())
+                .filter(|item| level.check(item))
+                .collect();
+            levels.push(level);
+            seeds_used.push(seed);
+            parity_is_included = !parity_is_included;
+            to_encode = residue;
+        }
+        Ok((BloomCascade { levels, fp_rate }, seeds_used))
+    }
+
+    /// Returns whether `item` (assumed to be a member of R ∪ S) is a member of R, with zero false
+    /// positives for any item actually drawn from R ∪ S.
+    pub fn query(&self, item: &[u8]) -> bool {
+        for (i, level) in self.levels.iter().enumerate() {
+            if !level.check(item) {
+                return i % 2 == 1;
+            }
+        }
+        self.levels.len() % 2 == 1
+    }
+
+    /// Number of levels the cascade built.
+    pub fn num_levels(&self) -> usize {
+        self.levels.len()
+    }
+
+    /// The configured fp rate shared by every level.
+    pub fn fp_rate(&self) -> f64 {
+        self.fp_rate
+    }
+
+    /// Per-level bitmap sizes in bits, for `BF.CASCADE.INFO`.
+    pub fn level_sizes(&self) -> Vec<u64> {
+        self.levels.iter().map(|level| level.bits_total()).collect()
+    }
+
+    /// Total memory usage of the cascade and every level it contains.
+    pub fn memory_usage(&self) -> usize {
+        std::mem::size_of::<BloomCascade>()
+            + self.levels.iter().map(|level| level.number_of_bytes()).sum::<usize>()
+    }
+
+    /// Serializes the cascade to a byte array, prefixed with `BLOOM_CASCADE_VERSION`.
+    pub fn encode_cascade(&self) -> Result<Vec<u8>, BloomError> {
+        let bytes = bincode::serialize(self).map_err(|_| BloomError::EncodeBloomFilterFailed)?;
+        let mut out = Vec::with_capacity(1 + bytes.len());
+        out.push(BLOOM_CASCADE_VERSION);
+        out.extend(bytes);
+        Ok(out)
+    }
+
+    /// Deserializes a cascade previously produced by `encode_cascade`. Mirrors
+    /// `BloomObject::decode_object`'s validation: a malformed or maliciously crafted blob can't resurrect
+    /// a cascade with an out-of-range `fp_rate` or more levels than `build` would ever produce.
+    pub fn decode_cascade(bytes: &[u8]) -> Result<BloomCascade, BloomError> {
+        if bytes.is_empty() {
+            return Err(BloomError::DecodeBloomFilterFailed);
+        }
+        match bytes[0] {
+            BLOOM_CASCADE_VERSION => {
+                let cascade = bincode::deserialize::<BloomCascade>(&bytes[1..])
+                    .map_err(|_| BloomError::DecodeBloomFilterFailed)?;
+                if !(cascade.fp_rate > BLOOM_FP_RATE_MIN && cascade.fp_rate < BLOOM_FP_RATE_MAX) {
+                    return Err(BloomError::ErrorRateRange);
+                }
+                if cascade.levels.len() > MAX_CASCADE_LEVELS {
+                    return Err(BloomError::DecodeBloomFilterFailed);
+                }
+                Ok(cascade)
+            }
+            _ => Err(BloomError::DecodeUnsupportedVersion),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cascade_exact_membership() {
+        let included: Vec<Vec<u8>> = (0..200).map(|i| format!("included-{i}").into_bytes()).collect();
+        let excluded: Vec<Vec<u8>> = (0..200).map(|i| format!("excluded-{i}").into_bytes()).collect();
+        let (cascade, _) = BloomCascade::build(&included, &excluded, 0.01);
+        for item in &included {
+            assert!(cascade.query(item), "expected {item:?} to be a member of R");
+        }
+        for item in &excluded {
+            assert!(!cascade.query(item), "expected {item:?} to not be a member of R");
+        }
+    }
+
+    #[test]
+    fn test_cascade_roundtrip() {
+        let included: Vec<Vec<u8>> = (0..50).map(|i| format!("r-{i}").into_bytes()).collect();
+        let excluded: Vec<Vec<u8>> = (0..50).map(|i| format!("s-{i}").into_bytes()).collect();
+        let (cascade, _) = BloomCascade::build(&included, &excluded, 0.01);
+        let bytes = cascade.encode_cascade().unwrap();
+        let restored = BloomCascade::decode_cascade(&bytes).unwrap();
+        assert_eq!(restored.num_levels(), cascade.num_levels());
+        for item in &included {
+            assert!(restored.query(item));
+        }
+        for item in &excluded {
+            assert!(!restored.query(item));
+        }
+    }
+
+    #[test]
+    fn test_decode_cascade_rejects_out_of_range_fp_rate() {
+        let included: Vec<Vec<u8>> = (0..10).map(|i| format!("r-{i}").into_bytes()).collect();
+        let excluded: Vec<Vec<u8>> = (0..10).map(|i| format!("s-{i}").into_bytes()).collect();
+        let (mut cascade, _) = BloomCascade::build(&included, &excluded, 0.01);
+        cascade.fp_rate = 1.5;
+        let mut bytes = bincode::serialize(&cascade).unwrap();
+        bytes.insert(0, BLOOM_CASCADE_VERSION);
+        assert_eq!(
+            BloomCascade::decode_cascade(&bytes).unwrap_err(),
+            BloomError::ErrorRateRange
+        );
+    }
+
+    #[test]
+    fn test_decode_cascade_rejects_too_many_levels() {
+        let mut cascade = BloomCascade {
+            levels: Vec::new(),
+            fp_rate: 0.01,
+        };
+        for _ in 0..=MAX_CASCADE_LEVELS {
+            cascade.levels.push(BloomFilter::with_random_seed(0.01, 1));
+        }
+        let mut bytes = bincode::serialize(&cascade).unwrap();
+        bytes.insert(0, BLOOM_CASCADE_VERSION);
+        assert_eq!(
+            BloomCascade::decode_cascade(&bytes).unwrap_err(),
+            BloomError::DecodeBloomFilterFailed
+        );
+    }
+}
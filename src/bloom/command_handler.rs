@@ -1,18 +1,62 @@
+use crate::bloom::cascade::BloomCascade;
+use crate::bloom::data_type::BLOOM_CASCADE_TYPE;
 use crate::bloom::data_type::BLOOM_TYPE;
 use crate::bloom::utils;
+use crate::bloom::utils::BloomError;
+use crate::bloom::utils::BloomFilter;
 use crate::bloom::utils::BloomObject;
 use crate::configs;
 use crate::configs::{
     BLOOM_CAPACITY_MAX, BLOOM_CAPACITY_MIN, BLOOM_EXPANSION_MAX, BLOOM_EXPANSION_MIN,
     BLOOM_FP_RATE_MAX, BLOOM_FP_RATE_MIN, BLOOM_TIGHTENING_RATIO_MAX, BLOOM_TIGHTENING_RATIO_MIN,
 };
+use lazy_static::lazy_static;
+use std::collections::HashMap;
 use std::sync::atomic::Ordering;
+use std::sync::Mutex;
 use valkey_module::ContextFlags;
 use valkey_module::NotifyEvent;
 use valkey_module::{Context, ValkeyError, ValkeyResult, ValkeyString, ValkeyValue, VALKEY_OK};
 
+/// Returns the number of worker threads to use for the parallel bulk-add path, bounded by the number of
+/// cores available on the host.
+fn num_cpus() -> usize {
+    std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+}
+
+/// Parses the optional `[bits]` operand of a `COUNTING [bits]` option shared by `BF.RESERVE`/`BF.INSERT`.
+/// `idx` points at the `COUNTING` token itself; if the following token parses as an in-range counter
+/// width it is consumed (`idx` is advanced past it) and returned; a number outside
+/// `BLOOM_COUNTING_BITS_MIN..=BLOOM_COUNTING_BITS_MAX` is rejected with `BAD_COUNTING_BITS`. If it is not a
+/// number at all (or there is no next token), `BLOOM_COUNTING_BITS_DEFAULT` is returned and `idx` is left
+/// untouched so the next token can still be parsed as another option.
+fn parse_optional_counting_bits(
+    args: &[ValkeyString],
+    idx: &mut usize,
+    argc: usize,
+) -> Result<u8, ValkeyError> {
+    if *idx + 1 < argc {
+        if let Ok(num) = args[*idx + 1].to_string_lossy().parse::<u8>() {
+            return if (configs::BLOOM_COUNTING_BITS_MIN..=configs::BLOOM_COUNTING_BITS_MAX)
+                .contains(&num)
+            {
+                *idx += 1;
+                Ok(num)
+            } else {
+                Err(ValkeyError::Str(utils::BAD_COUNTING_BITS))
+            };
+        }
+    }
+    Ok(configs::BLOOM_COUNTING_BITS_DEFAULT)
+}
+
 /// Helper function used to add items to a bloom object. It handles both multi item and single item add operations.
 /// It is used by any command that allows adding of items: BF.ADD, BF.MADD, and BF.INSERT.
+/// `jobs` is an explicit worker count from `BF.INSERT ... JOBS <count>`; when present it forces the
+/// parallel bulk-add path regardless of `bloom-bulk-parallel-threshold`, letting a caller populating a
+/// fresh filter from a large item set opt into parallel population without waiting for the threshold.
 /// Returns the result of the item add operation on success as a ValkeyValue and a ValkeyError on failure.
 fn handle_bloom_add(
     args: &[ValkeyString],
@@ -22,25 +66,48 @@ fn handle_bloom_add(
     multi: bool,
     add_succeeded: &mut bool,
     validate_size_limit: bool,
+    jobs: Option<usize>,
 ) -> Result<ValkeyValue, ValkeyError> {
     match multi {
         true => {
-            let mut result = Vec::new();
-            for item in args.iter().take(argc).skip(item_idx) {
-                match bf.add_item(item.as_slice(), validate_size_limit) {
-                    Ok(add_result) => {
-                        if add_result == 1 {
-                            *add_succeeded = true;
-                        }
-                        result.push(ValkeyValue::Integer(add_result));
-                    }
-                    Err(err) => {
-                        result.push(ValkeyValue::StaticError(err.as_str()));
-                        break;
+            let items: Vec<&[u8]> = args[item_idx..argc].iter().map(|i| i.as_slice()).collect();
+            let threshold = configs::BLOOM_BULK_PARALLEL_THRESHOLD.load(Ordering::Relaxed);
+            let num_threads = jobs.map_or_else(num_cpus, |j| j.min(num_cpus()));
+            // The parallel bulk-add path only handles the common case where every item lands in the
+            // currently active sub-filter without triggering a scale-out; it also skips the per-item
+            // existence check the serial path does, so it always reports every item as newly added.
+            // It also doesn't maintain per-slot counters, so counting-mode objects always skip straight to
+            // `add_items_bulk` below, which does merge counters correctly for batches that need to span a
+            // scale-out or that are simply too large for the single-filter path above to apply.
+            // We fall back to `add_items_bulk` below on any other outcome (scale-out needed, errors, etc.)
+            if !bf.is_counting()
+                && (jobs.is_some() || items.len() as i64 > threshold)
+                && bf.add_items_parallel(&items, num_threads).is_ok()
+            {
+                *add_succeeded = true;
+                return Ok(ValkeyValue::Array(vec![
+                    ValkeyValue::Integer(1);
+                    items.len()
+                ]));
+            }
+            // Otherwise, still add the whole batch in one `add_items_bulk` call rather than looping
+            // `add_item` per item - it amortizes the per-item existence scan and scale-out decision
+            // across the batch instead of repeating both for every item, and parallelizes the actual
+            // bit-setting across however many sub-filters the batch spans (unlike `add_items_parallel`
+            // above, which only handles the case where everything fits in the currently active filter).
+            match bf.add_items_bulk(&items, validate_size_limit) {
+                Ok(add_results) => {
+                    if add_results.iter().any(|&r| r == 1) {
+                        *add_succeeded = true;
                     }
-                };
+                    Ok(ValkeyValue::Array(
+                        add_results.into_iter().map(ValkeyValue::Integer).collect(),
+                    ))
+                }
+                Err(err) => Ok(ValkeyValue::Array(vec![ValkeyValue::StaticError(
+                    err.as_str(),
+                )])),
             }
-            Ok(ValkeyValue::Array(result))
         }
         false => {
             let item = args[item_idx].as_slice();
@@ -62,6 +129,7 @@ struct ReplicateArgs<'a> {
     fp_rate: f64,
     tightening_ratio: f64,
     seed: [u8; 32],
+    counting_bits: Option<u8>,
     items: &'a [ValkeyString],
 }
 
@@ -134,6 +202,20 @@ fn replicate_and_notify_events(
         for arg in &expansion_args {
             cmd.push(arg);
         }
+        // Carry the counting mode/width through so replicas create bit-identical (counting or plain)
+        // objects instead of defaulting to a plain bloom object.
+        let counting_args = args.counting_bits.map(|bits| {
+            let counting_str =
+                ValkeyString::create_from_slice(std::ptr::null_mut(), "COUNTING".as_bytes());
+            let counting_val =
+                ValkeyString::create_from_slice(std::ptr::null_mut(), bits.to_string().as_bytes());
+            [counting_str, counting_val]
+        });
+        if let Some(counting_args) = &counting_args {
+            for arg in counting_args {
+                cmd.push(arg);
+            }
+        }
         // Add items if any exist.
         let items_str = ValkeyString::create_from_slice(std::ptr::null_mut(), "ITEMS".as_bytes());
         if !args.items.is_empty() {
@@ -189,6 +271,7 @@ pub fn bloom_filter_add_value(
                 multi,
                 &mut add_succeeded,
                 validate_size_limit,
+                None,
             );
             let replicate_args = ReplicateArgs {
                 capacity: bloom.capacity(),
@@ -196,6 +279,7 @@ pub fn bloom_filter_add_value(
                 fp_rate: bloom.fp_rate(),
                 tightening_ratio: bloom.tightening_ratio(),
                 seed: bloom.seed(),
+                counting_bits: bloom.counting_bits(),
                 items: &input_args[curr_cmd_idx..],
             };
             replicate_and_notify_events(ctx, filter_name, add_succeeded, false, replicate_args);
@@ -233,6 +317,7 @@ pub fn bloom_filter_add_value(
                 fp_rate: bloom.fp_rate(),
                 tightening_ratio: bloom.tightening_ratio(),
                 seed: bloom.seed(),
+                counting_bits: bloom.counting_bits(),
                 items: &input_args[curr_cmd_idx..],
             };
             let response = handle_bloom_add(
@@ -243,6 +328,7 @@ pub fn bloom_filter_add_value(
                 multi,
                 &mut add_succeeded,
                 validate_size_limit,
+                None,
             );
             match filter_key.set_value(&BLOOM_TYPE, bloom) {
                 Ok(()) => {
@@ -261,6 +347,43 @@ pub fn bloom_filter_add_value(
     }
 }
 
+/// Function that implements logic to handle the BF.DEL command.
+/// `BF.DEL <key> <item> [item ...]` removes one or more items from a counting-mode bloom object,
+/// mirroring `handle_bloom_add`'s shape but over the delete path: it decrements every counter each item
+/// maps to instead of incrementing them. Unlike BF.ADD/BF.INSERT, BF.DEL never creates a key - deleting
+/// from a key that does not exist, or from a plain (non-counting) bloom object, is an error.
+pub fn bloom_filter_delete(ctx: &Context, input_args: &[ValkeyString]) -> ValkeyResult {
+    let argc = input_args.len();
+    if argc < 3 {
+        return Err(ValkeyError::WrongArity);
+    }
+    let filter_name = &input_args[1];
+    let filter_key = ctx.open_key_writable(filter_name);
+    let bloom = match filter_key.get_value::<BloomObject>(&BLOOM_TYPE) {
+        Ok(Some(v)) => v,
+        Ok(None) => return Err(ValkeyError::Str(utils::NOT_FOUND)),
+        Err(_) => return Err(ValkeyError::WrongType),
+    };
+    let mut delete_succeeded = false;
+    let mut result = Vec::new();
+    for item in &input_args[2..] {
+        match bloom.delete_item(item.as_slice()) {
+            Ok(delete_result) => {
+                if delete_result == 1 {
+                    delete_succeeded = true;
+                }
+                result.push(ValkeyValue::Integer(delete_result));
+            }
+            Err(err) => return Err(ValkeyError::Str(err.as_str())),
+        }
+    }
+    if delete_succeeded {
+        ctx.replicate_verbatim();
+        ctx.notify_keyspace_event(NotifyEvent::GENERIC, utils::DEL_EVENT, filter_name);
+    }
+    Ok(ValkeyValue::Array(result))
+}
+
 /// Helper function used to check whether an item (or multiple items) exists on a bloom object.
 fn handle_item_exists(value: Option<&BloomObject>, item: &[u8]) -> ValkeyValue {
     if let Some(val) = value {
@@ -300,13 +423,32 @@ pub fn bloom_filter_exists(
         let item = input_args[curr_cmd_idx].as_slice();
         return Ok(handle_item_exists(value, item));
     }
-    let mut result = Vec::new();
-    while curr_cmd_idx < argc {
-        let item = input_args[curr_cmd_idx].as_slice();
-        result.push(handle_item_exists(value, item));
-        curr_cmd_idx += 1;
+    let Some(bf) = value else {
+        return Ok(ValkeyValue::Array(vec![
+            ValkeyValue::Integer(0);
+            argc - curr_cmd_idx
+        ]));
+    };
+    let items: Vec<&[u8]> = input_args[curr_cmd_idx..argc]
+        .iter()
+        .map(|i| i.as_slice())
+        .collect();
+    let threshold = configs::BLOOM_BULK_PARALLEL_THRESHOLD.load(Ordering::Relaxed);
+    if items.len() as i64 > threshold {
+        let num_threads = num_cpus();
+        return Ok(ValkeyValue::Array(
+            bf.check_items_parallel(&items, num_threads)
+                .into_iter()
+                .map(|found| ValkeyValue::Integer(found as i64))
+                .collect(),
+        ));
     }
-    Ok(ValkeyValue::Array(result))
+    Ok(ValkeyValue::Array(
+        items
+            .into_iter()
+            .map(|item| ValkeyValue::Integer(bf.item_exists(item) as i64))
+            .collect(),
+    ))
 }
 
 /// Function that implements logic to handle the BF.CARD command.
@@ -334,7 +476,7 @@ pub fn bloom_filter_card(ctx: &Context, input_args: &[ValkeyString]) -> ValkeyRe
 /// Function that implements logic to handle the BF.RESERVE command.
 pub fn bloom_filter_reserve(ctx: &Context, input_args: &[ValkeyString]) -> ValkeyResult {
     let argc = input_args.len();
-    if !(4..=6).contains(&argc) {
+    if argc < 4 {
         return Err(ValkeyError::WrongArity);
     }
     let mut curr_cmd_idx = 1;
@@ -364,16 +506,22 @@ pub fn bloom_filter_reserve(ctx: &Context, input_args: &[ValkeyString]) -> Valke
     };
     curr_cmd_idx += 1;
     let mut expansion = configs::BLOOM_EXPANSION.load(Ordering::Relaxed) as u32;
-    if argc > 4 {
+    let mut counting_bits: Option<u8> = None;
+    let mut sbbf = false;
+    let mut murmur128 = false;
+    while curr_cmd_idx < argc {
         match input_args[curr_cmd_idx]
             .to_string_lossy()
             .to_uppercase()
             .as_str()
         {
-            "NONSCALING" if argc == 5 => {
+            "NONSCALING" => {
                 expansion = 0;
             }
-            "EXPANSION" if argc == 6 => {
+            "EXPANSION" => {
+                if curr_cmd_idx >= argc - 1 {
+                    return Err(ValkeyError::WrongArity);
+                }
                 curr_cmd_idx += 1;
                 expansion = match input_args[curr_cmd_idx].to_string_lossy().parse::<u32>() {
                     Ok(num) if (BLOOM_EXPANSION_MIN..=BLOOM_EXPANSION_MAX).contains(&num) => num,
@@ -382,10 +530,53 @@ pub fn bloom_filter_reserve(ctx: &Context, input_args: &[ValkeyString]) -> Valke
                     }
                 };
             }
+            "COUNTING" => {
+                counting_bits = Some(parse_optional_counting_bits(
+                    input_args,
+                    &mut curr_cmd_idx,
+                    argc,
+                )?);
+            }
+            "SBBF" => {
+                sbbf = true;
+            }
+            "HASH" => {
+                if curr_cmd_idx >= argc - 1 {
+                    return Err(ValkeyError::WrongArity);
+                }
+                curr_cmd_idx += 1;
+                murmur128 = match input_args[curr_cmd_idx]
+                    .to_string_lossy()
+                    .to_uppercase()
+                    .as_str()
+                {
+                    "DEFAULT" => false,
+                    "MURMUR128" => true,
+                    _ => return Err(ValkeyError::Str(utils::BAD_HASH_ALGORITHM)),
+                };
+            }
             _ => {
                 return Err(ValkeyError::Str(utils::ERROR));
             }
         }
+        curr_cmd_idx += 1;
+    }
+    if sbbf && (counting_bits.is_some() || expansion != configs::BLOOM_EXPANSION.load(Ordering::Relaxed) as u32) {
+        return Err(ValkeyError::Str(utils::ERROR));
+    }
+    if murmur128
+        && (sbbf
+            || counting_bits.is_some()
+            || expansion != configs::BLOOM_EXPANSION.load(Ordering::Relaxed) as u32)
+    {
+        return Err(ValkeyError::Str(utils::ERROR));
+    }
+    // `bloom-filter-algorithm ribbon` only applies to a `NONSCALING` reserve with no `COUNTING`; a scaling
+    // reserve under that config silently falls back to the standard bloom backend below instead of erroring,
+    // since the global config isn't something the caller of this particular command chose.
+    let ribbon = configs::filter_algorithm_is_ribbon() && !sbbf && !murmur128 && expansion == 0;
+    if ribbon && counting_bits.is_some() {
+        return Err(ValkeyError::Str(utils::RIBBON_REQUIRES_NONSCALING));
     }
     // If the filter does not exist, create one
     let filter_key = ctx.open_key_writable(filter_name);
@@ -397,6 +588,126 @@ pub fn bloom_filter_reserve(ctx: &Context, input_args: &[ValkeyString]) -> Valke
     };
     match value {
         Some(_) => Err(ValkeyError::Str(utils::ITEM_EXISTS)),
+        None if sbbf => {
+            // A Split Block Bloom Filter is a fixed-size, Parquet-compatible bitset rather than a scaling
+            // set of sub-filters, so it can't be deterministically rebuilt via `BF.INSERT ... CAPACITY ...`
+            // like a standard RESERVE (see `replicate_and_notify_events`). Replicate verbatim instead, the
+            // same way `BF.CASCADE.BUILD` does for its own separate filter kind.
+            let validate_size_limit = !ctx.get_flags().contains(ContextFlags::REPLICATED);
+            let bloom = match BloomObject::new_reserved_sbbf(capacity, fp_rate, validate_size_limit)
+            {
+                Ok(bf) => bf,
+                Err(err) => return Err(ValkeyError::Str(err.as_str())),
+            };
+            match filter_key.set_value(&BLOOM_TYPE, bloom) {
+                Ok(()) => {
+                    ctx.replicate_verbatim();
+                    ctx.notify_keyspace_event(NotifyEvent::GENERIC, utils::RESERVE_EVENT, filter_name);
+                    VALKEY_OK
+                }
+                Err(_) => Err(ValkeyError::Str(utils::ERROR)),
+            }
+        }
+        None if murmur128 => {
+            // Like SBBF above, a murmur128-backed object is a single fixed-size filter rather than a
+            // scaling set of sub-filters, so it can't be deterministically rebuilt via `BF.INSERT ...`;
+            // replicate verbatim instead.
+            let use_random_seed = configs::BLOOM_USE_RANDOM_SEED.load(Ordering::Relaxed);
+            let murmur_seed = if use_random_seed {
+                // `RandomState`'s keys are seeded from the OS RNG on construction, so hashing anything
+                // with it yields an arbitrary, unpredictable u32 without pulling in a dedicated RNG crate
+                // just for this.
+                use std::hash::{BuildHasher, Hasher};
+                std::collections::hash_map::RandomState::new()
+                    .build_hasher()
+                    .finish() as u32
+            } else {
+                u32::from_le_bytes(
+                    configs::FIXED_SEED[..4]
+                        .try_into()
+                        .expect("slice of length 4 always converts"),
+                )
+            };
+            let validate_size_limit = !ctx.get_flags().contains(ContextFlags::REPLICATED);
+            let bloom = match BloomObject::new_reserved_murmur128(
+                capacity,
+                fp_rate,
+                murmur_seed,
+                validate_size_limit,
+            ) {
+                Ok(bf) => bf,
+                Err(err) => return Err(ValkeyError::Str(err.as_str())),
+            };
+            match filter_key.set_value(&BLOOM_TYPE, bloom) {
+                Ok(()) => {
+                    ctx.replicate_verbatim();
+                    ctx.notify_keyspace_event(NotifyEvent::GENERIC, utils::RESERVE_EVENT, filter_name);
+                    VALKEY_OK
+                }
+                Err(_) => Err(ValkeyError::Str(utils::ERROR)),
+            }
+        }
+        None if ribbon => {
+            // Like SBBF/murmur128 above, a Ribbon-backed object is a single fixed-size filter rather than a
+            // scaling set of sub-filters, and its 32-bit murmur3 seed doesn't fit the generic `ReplicateArgs`
+            // (sized for the sip-hash seed); replicate verbatim instead. Subsequent `BF.ADD`s propagate
+            // themselves as usual and are unaffected by this.
+            let use_random_seed = configs::BLOOM_USE_RANDOM_SEED.load(Ordering::Relaxed);
+            let ribbon_seed = if use_random_seed {
+                use std::hash::{BuildHasher, Hasher};
+                std::collections::hash_map::RandomState::new()
+                    .build_hasher()
+                    .finish() as u32
+            } else {
+                u32::from_le_bytes(
+                    configs::FIXED_SEED[..4]
+                        .try_into()
+                        .expect("slice of length 4 always converts"),
+                )
+            };
+            let validate_size_limit = !ctx.get_flags().contains(ContextFlags::REPLICATED);
+            let bloom = match BloomObject::new_reserved_ribbon(
+                capacity,
+                fp_rate,
+                ribbon_seed,
+                validate_size_limit,
+            ) {
+                Ok(bf) => bf,
+                Err(BloomError::ExceedsGlobalMemoryBudget) => {
+                    // Under global memory pressure, fall back to the standard bloom backend instead of
+                    // failing the write outright: its banding/peak allocation cost is lower and bounded,
+                    // unlike a Ribbon filter's solved matrix. See `bloom-total-memory-limit`.
+                    let seed = match use_random_seed {
+                        true => (None, true),
+                        false => (Some(configs::FIXED_SEED), false),
+                    };
+                    let tightening_ratio = *configs::BLOOM_TIGHTENING_F64
+                        .lock()
+                        .expect("Unable to get a lock on tightening ratio static");
+                    match BloomObject::new_reserved_with_counting(
+                        fp_rate,
+                        tightening_ratio,
+                        capacity,
+                        0,
+                        seed,
+                        validate_size_limit,
+                        None,
+                    ) {
+                        Ok(bf) => bf,
+                        Err(err) => return Err(ValkeyError::Str(err.as_str())),
+                    }
+                }
+                Err(err) => return Err(ValkeyError::Str(err.as_str())),
+            };
+            match filter_key.set_value(&BLOOM_TYPE, bloom) {
+                Ok(()) => {
+                    ctx.replicate_verbatim();
+                    ctx.notify_keyspace_event(NotifyEvent::GENERIC, utils::RESERVE_EVENT, filter_name);
+                    VALKEY_OK
+                }
+                Err(_) => Err(ValkeyError::Str(utils::ERROR)),
+            }
+        }
         None => {
             let use_random_seed = configs::BLOOM_USE_RANDOM_SEED.load(Ordering::Relaxed);
             let seed = match use_random_seed {
@@ -408,13 +719,14 @@ pub fn bloom_filter_reserve(ctx: &Context, input_args: &[ValkeyString]) -> Valke
             let tightening_ratio = *configs::BLOOM_TIGHTENING_F64
                 .lock()
                 .expect("Unable to get a lock on tightening ratio static");
-            let bloom = match BloomObject::new_reserved(
+            let bloom = match BloomObject::new_reserved_with_counting(
                 fp_rate,
                 tightening_ratio,
                 capacity,
                 expansion,
                 seed,
                 validate_size_limit,
+                counting_bits,
             ) {
                 Ok(bf) => bf,
                 Err(err) => return Err(ValkeyError::Str(err.as_str())),
@@ -425,6 +737,7 @@ pub fn bloom_filter_reserve(ctx: &Context, input_args: &[ValkeyString]) -> Valke
                 fp_rate: bloom.fp_rate(),
                 tightening_ratio: bloom.tightening_ratio(),
                 seed: bloom.seed(),
+                counting_bits: bloom.counting_bits(),
                 items: &[],
             };
             match filter_key.set_value(&BLOOM_TYPE, bloom) {
@@ -464,6 +777,8 @@ pub fn bloom_filter_insert(ctx: &Context, input_args: &[ValkeyString]) -> Valkey
     };
     let mut nocreate = false;
     let mut items_provided = false;
+    let mut counting_bits: Option<u8> = None;
+    let mut jobs: Option<usize> = None;
     while idx < argc {
         match input_args[idx].to_string_lossy().to_uppercase().as_str() {
             "ERROR" => {
@@ -553,6 +868,21 @@ pub fn bloom_filter_insert(ctx: &Context, input_args: &[ValkeyString]) -> Valkey
                     }
                 };
             }
+            "COUNTING" => {
+                counting_bits = Some(parse_optional_counting_bits(input_args, &mut idx, argc)?);
+            }
+            "JOBS" => {
+                if idx >= (argc - 1) {
+                    return Err(ValkeyError::WrongArity);
+                }
+                idx += 1;
+                jobs = match input_args[idx].to_string_lossy().parse::<usize>() {
+                    Ok(num) if num > 0 => Some(num),
+                    _ => {
+                        return Err(ValkeyError::Str(utils::BAD_JOBS_COUNT));
+                    }
+                };
+            }
             "ITEMS" => {
                 idx += 1;
                 items_provided = true;
@@ -581,6 +911,14 @@ pub fn bloom_filter_insert(ctx: &Context, input_args: &[ValkeyString]) -> Valkey
     let mut add_succeeded = false;
     match value {
         Some(bloom) => {
+            // An explicit `COUNTING` option only applies to a freshly created filter; on an existing one
+            // it must match the filter's actual counting mode, since silently ignoring a mismatch would
+            // let a caller believe `BF.DEL` is available when it isn't (or vice versa).
+            if let Some(requested) = counting_bits {
+                if bloom.counting_bits() != Some(requested) {
+                    return Err(ValkeyError::Str(utils::COUNTING_MODE_MISMATCH));
+                }
+            }
             let response = handle_bloom_add(
                 input_args,
                 argc,
@@ -589,6 +927,7 @@ pub fn bloom_filter_insert(ctx: &Context, input_args: &[ValkeyString]) -> Valkey
                 true,
                 &mut add_succeeded,
                 validate_size_limit,
+                jobs,
             );
             let replicate_args = ReplicateArgs {
                 capacity: bloom.capacity(),
@@ -596,6 +935,7 @@ pub fn bloom_filter_insert(ctx: &Context, input_args: &[ValkeyString]) -> Valkey
                 fp_rate: bloom.fp_rate(),
                 tightening_ratio: bloom.tightening_ratio(),
                 seed: bloom.seed(),
+                counting_bits: bloom.counting_bits(),
                 items: &input_args[idx..],
             };
             replicate_and_notify_events(ctx, filter_name, add_succeeded, false, replicate_args);
@@ -605,13 +945,14 @@ pub fn bloom_filter_insert(ctx: &Context, input_args: &[ValkeyString]) -> Valkey
             if nocreate {
                 return Err(ValkeyError::Str(utils::NOT_FOUND));
             }
-            let mut bloom = match BloomObject::new_reserved(
+            let mut bloom = match BloomObject::new_reserved_with_counting(
                 fp_rate,
                 tightening_ratio,
                 capacity,
                 expansion,
                 seed,
                 validate_size_limit,
+                counting_bits,
             ) {
                 Ok(bf) => bf,
                 Err(err) => return Err(ValkeyError::Str(err.as_str())),
@@ -622,6 +963,7 @@ pub fn bloom_filter_insert(ctx: &Context, input_args: &[ValkeyString]) -> Valkey
                 fp_rate: bloom.fp_rate(),
                 tightening_ratio: bloom.tightening_ratio(),
                 seed: bloom.seed(),
+                counting_bits: bloom.counting_bits(),
                 items: &input_args[idx..],
             };
             let response = handle_bloom_add(
@@ -632,6 +974,7 @@ pub fn bloom_filter_insert(ctx: &Context, input_args: &[ValkeyString]) -> Valkey
                 true,
                 &mut add_succeeded,
                 validate_size_limit,
+                jobs,
             );
             match filter_key.set_value(&BLOOM_TYPE, bloom) {
                 Ok(()) => {
@@ -650,6 +993,96 @@ pub fn bloom_filter_insert(ctx: &Context, input_args: &[ValkeyString]) -> Valkey
     }
 }
 
+/// Function that implements logic to handle the BF.MERGE command.
+/// `BF.MERGE <dest> <src1> [<src2> ...]` creates `dest` from the set-union of the sources' bitmaps,
+/// requiring every source to be bit-compatible with each other (identical capacity, fp_rate, expansion,
+/// tightening_ratio and seed, and the same number of sub-filters for scaled filters). `dest` is created if
+/// absent, or overwritten (not merged into) if it already exists. This enables the sharded build pattern
+/// where independent workers each populate their own filter over a partition of the input and the results
+/// are combined at the end.
+pub fn bloom_filter_merge(ctx: &Context, input_args: &[ValkeyString]) -> ValkeyResult {
+    let argc = input_args.len();
+    if argc < 3 {
+        return Err(ValkeyError::WrongArity);
+    }
+    let dest_name = &input_args[1];
+    let dest_key = ctx.open_key_writable(dest_name);
+    if dest_key.get_value::<BloomObject>(&BLOOM_TYPE).is_err() {
+        return Err(ValkeyError::WrongType);
+    }
+
+    let mut src_keys = Vec::with_capacity(input_args.len() - 2);
+    for src_name in &input_args[2..] {
+        src_keys.push(ctx.open_key(src_name));
+    }
+    let mut sources = Vec::with_capacity(src_keys.len());
+    for src_key in &src_keys {
+        match src_key.get_value::<BloomObject>(&BLOOM_TYPE) {
+            Ok(Some(v)) => sources.push(v),
+            Ok(None) => return Err(ValkeyError::Str(utils::NOT_FOUND)),
+            Err(_) => return Err(ValkeyError::WrongType),
+        }
+    }
+
+    let mut dest = BloomObject::create_copy_from(sources[0]);
+    for src in &sources[1..] {
+        if let Err(err) = dest.merge_from(src) {
+            return Err(ValkeyError::Str(err.as_str()));
+        }
+    }
+
+    match dest_key.set_value(&BLOOM_TYPE, dest) {
+        Ok(_) => {
+            ctx.replicate_verbatim();
+            ctx.notify_keyspace_event(NotifyEvent::GENERIC, utils::RESERVE_EVENT, dest_name);
+            ctx.notify_keyspace_event(NotifyEvent::GENERIC, utils::MERGE_EVENT, dest_name);
+            VALKEY_OK
+        }
+        Err(_) => Err(ValkeyError::Str(utils::ERROR)),
+    }
+}
+
+/// Builds the per-sub-filter byte/fill-ratio breakdown shared by `BF.INFO ... MEMORY` and the general
+/// `BF.INFO <key>` summary's "Filters detail" field. Bits are reported as raw `set`/`total` counts rather
+/// than a pre-divided ratio so the reply stays integer-only; a sub-filter near 100% fill predicts that the
+/// object is about to scale out.
+fn filter_memory_details(val: &BloomObject) -> Vec<ValkeyValue> {
+    val.filter_memory_breakdown()
+        .into_iter()
+        .map(|(bytes, bits_set, bits_total)| {
+            ValkeyValue::Array(vec![
+                ValkeyValue::SimpleStringStatic("Bytes"),
+                ValkeyValue::Integer(bytes as i64),
+                ValkeyValue::SimpleStringStatic("Bits set"),
+                ValkeyValue::Integer(bits_set as i64),
+                ValkeyValue::SimpleStringStatic("Bits total"),
+                ValkeyValue::Integer(bits_total as i64),
+            ])
+        })
+        .collect()
+}
+
+/// Builds the per-sub-filter capacity/items/bytes/fill-ratio breakdown for `BF.INFO ... FILTERSDETAIL`.
+/// Fill ratio is reported as a string (rather than `filter_memory_details`'s raw set/total bit counts)
+/// since it's meant to be read directly rather than combined further.
+fn filters_detail(val: &BloomObject) -> Vec<ValkeyValue> {
+    val.filter_detail_breakdown()
+        .into_iter()
+        .map(|(capacity, items, bytes, fill_ratio)| {
+            ValkeyValue::Array(vec![
+                ValkeyValue::SimpleStringStatic("Capacity"),
+                ValkeyValue::Integer(capacity),
+                ValkeyValue::SimpleStringStatic("Items"),
+                ValkeyValue::Integer(items),
+                ValkeyValue::SimpleStringStatic("Bytes"),
+                ValkeyValue::Integer(bytes as i64),
+                ValkeyValue::SimpleStringStatic("Fill ratio"),
+                ValkeyValue::BulkString(fill_ratio.to_string().into_bytes()),
+            ])
+        })
+        .collect()
+}
+
 /// Function that implements logic to handle the BF.INFO command.
 pub fn bloom_filter_info(ctx: &Context, input_args: &[ValkeyString]) -> ValkeyResult {
     let argc = input_args.len();
@@ -684,6 +1117,25 @@ pub fn bloom_filter_info(ctx: &Context, input_args: &[ValkeyString]) -> ValkeyRe
                     }
                     Ok(ValkeyValue::Integer(val.expansion() as i64))
                 }
+                "MEMORY" => Ok(ValkeyValue::Array(vec![
+                    ValkeyValue::SimpleStringStatic("Total bytes"),
+                    ValkeyValue::Integer(val.memory_usage() as i64),
+                    ValkeyValue::SimpleStringStatic("Overhead bytes"),
+                    ValkeyValue::Integer(val.overhead_bytes() as i64),
+                    ValkeyValue::SimpleStringStatic("Filters"),
+                    ValkeyValue::Array(filter_memory_details(val)),
+                ])),
+                "FILLRATIO" => Ok(ValkeyValue::BulkString(
+                    val.fill_ratio().to_string().into_bytes(),
+                )),
+                "ERROR" => Ok(ValkeyValue::BulkString(
+                    val.fp_rate().to_string().into_bytes(),
+                )),
+                "CURRENTERROR" => Ok(ValkeyValue::BulkString(
+                    val.current_error_rate().to_string().into_bytes(),
+                )),
+                "FILTERSDETAIL" => Ok(ValkeyValue::Array(filters_detail(val))),
+                "SEED" => Ok(ValkeyValue::BulkString(seed_hex(val).into_bytes())),
                 _ => Err(ValkeyError::Str(utils::INVALID_INFO_VALUE)),
             }
         }
@@ -704,12 +1156,35 @@ pub fn bloom_filter_info(ctx: &Context, input_args: &[ValkeyString]) -> ValkeyRe
             } else {
                 result.push(ValkeyValue::Integer(val.expansion() as i64));
             }
+            result.push(ValkeyValue::SimpleStringStatic("Filters detail"));
+            result.push(ValkeyValue::Array(filter_memory_details(val)));
+            result.push(ValkeyValue::SimpleStringStatic("Fill ratio"));
+            result.push(ValkeyValue::BulkString(
+                val.fill_ratio().to_string().into_bytes(),
+            ));
+            result.push(ValkeyValue::SimpleStringStatic("Error rate"));
+            result.push(ValkeyValue::BulkString(
+                val.fp_rate().to_string().into_bytes(),
+            ));
+            result.push(ValkeyValue::SimpleStringStatic("Current error rate"));
+            result.push(ValkeyValue::BulkString(
+                val.current_error_rate().to_string().into_bytes(),
+            ));
+            result.push(ValkeyValue::SimpleStringStatic("Seed"));
+            result.push(ValkeyValue::BulkString(seed_hex(val).into_bytes()));
             Ok(ValkeyValue::Array(result))
         }
         _ => Err(ValkeyError::Str(utils::NOT_FOUND)),
     }
 }
 
+/// Renders a bloom object's hash seed (`BloomObject::seed`) as a lowercase hex string for `BF.INFO ...
+/// SEED`/the general `BF.INFO <key>` listing. `BF.INSERT ... SEED` takes the same 32 bytes raw, not as
+/// this hex text; `BF.RESERVE` has no `SEED` option.
+fn seed_hex(val: &BloomObject) -> String {
+    val.seed().iter().map(|b| format!("{b:02x}")).collect()
+}
+
 /// Function that implements logic to handle the BF.LOAD command.
 pub fn bloom_filter_load(ctx: &Context, input_args: &[ValkeyString]) -> ValkeyResult {
     let argc = input_args.len();
@@ -751,6 +1226,7 @@ pub fn bloom_filter_load(ctx: &Context, input_args: &[ValkeyString]) -> ValkeyRe
                 fp_rate: bloom.fp_rate(),
                 tightening_ratio: bloom.tightening_ratio(),
                 seed: bloom.seed(),
+                counting_bits: bloom.counting_bits(),
                 items: &input_args[idx..],
             };
             match filter_key.set_value(&BLOOM_TYPE, bloom) {
@@ -763,3 +1239,417 @@ pub fn bloom_filter_load(ctx: &Context, input_args: &[ValkeyString]) -> ValkeyRe
         }
     }
 }
+
+/// Function that implements logic to handle the BF.EXPORT command.
+/// `BF.EXPORT <key>` returns `key`'s Split Block Bloom Filter as a blob whose bitset body is
+/// Parquet/Arrow-wire-compatible, prefixed with a header private to this module (see
+/// `SplitBlockFilter::export` - it is NOT Parquet's Thrift-encoded `BloomFilterHeader`, so the whole blob
+/// isn't directly readable by a generic Parquet reader). Only supported for filters created with
+/// `BF.RESERVE ... SBBF`; pairs with `BF.IMPORT`.
+pub fn bloom_filter_export(ctx: &Context, input_args: &[ValkeyString]) -> ValkeyResult {
+    let argc = input_args.len();
+    if argc != 2 {
+        return Err(ValkeyError::WrongArity);
+    }
+    let filter_name = &input_args[1];
+    let filter_key = ctx.open_key(filter_name);
+    let bf = match filter_key.get_value::<BloomObject>(&BLOOM_TYPE) {
+        Ok(Some(v)) => v,
+        Ok(None) => return Err(ValkeyError::Str(utils::NOT_FOUND)),
+        Err(_) => return Err(ValkeyError::WrongType),
+    };
+    match bf.export_sbbf() {
+        Ok(bytes) => Ok(ValkeyValue::BulkString(bytes)),
+        Err(err) => Err(ValkeyError::Str(err.as_str())),
+    }
+}
+
+/// Function that implements logic to handle the BF.IMPORT command.
+/// `BF.IMPORT <key> <ndv> <fp_rate> <data>` creates `key` as an SBBF-backed filter from a `BF.EXPORT`-
+/// produced blob (see `SplitBlockFilter::import` for the caveat on its header not being Parquet's own).
+/// `ndv` is the number of distinct values the caller knows the filter was built for, since the Parquet
+/// wire format itself doesn't carry it. `key` must not already exist.
+pub fn bloom_filter_import(ctx: &Context, input_args: &[ValkeyString]) -> ValkeyResult {
+    let argc = input_args.len();
+    if argc != 5 {
+        return Err(ValkeyError::WrongArity);
+    }
+    let filter_name = &input_args[1];
+    let ndv = match input_args[2].to_string_lossy().parse::<i64>() {
+        Ok(num) => num,
+        Err(_) => return Err(ValkeyError::Str(utils::BAD_CAPACITY)),
+    };
+    let fp_rate = match input_args[3].to_string_lossy().parse::<f64>() {
+        Ok(num) => num,
+        Err(_) => return Err(ValkeyError::Str(utils::BAD_ERROR_RATE)),
+    };
+    let data = input_args[4].as_slice();
+    let filter_key = ctx.open_key_writable(filter_name);
+    match filter_key.get_value::<BloomObject>(&BLOOM_TYPE) {
+        Ok(Some(_)) => return Err(ValkeyError::Str(utils::KEY_EXISTS)),
+        Ok(None) => {}
+        Err(_) => return Err(ValkeyError::WrongType),
+    }
+    let validate_size_limit = !ctx.get_flags().contains(ContextFlags::REPLICATED);
+    let bloom = match BloomObject::new_imported_sbbf(data, ndv, fp_rate, validate_size_limit) {
+        Ok(v) => v,
+        Err(err) => return Err(ValkeyError::Str(err.as_str())),
+    };
+    match filter_key.set_value(&BLOOM_TYPE, bloom) {
+        Ok(_) => {
+            ctx.replicate_verbatim();
+            ctx.notify_keyspace_event(NotifyEvent::GENERIC, utils::RESERVE_EVENT, filter_name);
+            VALKEY_OK
+        }
+        Err(_) => Err(ValkeyError::Str(utils::ERROR)),
+    }
+}
+
+/// In-progress `BF.LOADCHUNK` transfers, keyed by destination key name, holding every sub-filter chunk
+/// received so far. A transfer is created by the chunk-0 (header) call and removed once either every
+/// chunk has arrived and the object has been installed, or the transfer is abandoned due to an error.
+struct PendingLoadChunk {
+    expansion: u32,
+    fp_rate: f64,
+    tightening_ratio: f64,
+    is_seed_random: bool,
+    num_filters: usize,
+    filters: Vec<Box<BloomFilter>>,
+    counting_bits: Option<u8>,
+}
+
+lazy_static! {
+    static ref LOADCHUNK_TRANSFERS: Mutex<HashMap<Vec<u8>, PendingLoadChunk>> =
+        Mutex::new(HashMap::new());
+}
+
+/// Function that implements logic to handle the BF.SCANDUMP command.
+/// `BF.SCANDUMP <key> <iterator>` returns `(next_iterator, chunk)` for the given `iterator`, where
+/// iterator `0` yields the object's header and each following iterator yields one sub-filter's bitmap.
+/// `next_iterator` is `0` once the final chunk has been returned, mirroring the SCAN family's cursor
+/// convention. This keeps the memory and command size needed to dump a (possibly multi-gigabyte, scaled)
+/// filter bounded to a single sub-filter at a time, and pairs with `BF.LOADCHUNK` to restore it.
+pub fn bloom_filter_scandump(ctx: &Context, input_args: &[ValkeyString]) -> ValkeyResult {
+    let argc = input_args.len();
+    if argc != 3 {
+        return Err(ValkeyError::WrongArity);
+    }
+    let filter_name = &input_args[1];
+    let iterator = match input_args[2].to_string_lossy().parse::<usize>() {
+        Ok(num) => num,
+        Err(_) => return Err(ValkeyError::Str(utils::INVALID_CHUNK)),
+    };
+    let filter_key = ctx.open_key(filter_name);
+    let bf = match filter_key.get_value::<BloomObject>(&BLOOM_TYPE) {
+        Ok(Some(v)) => v,
+        Ok(None) => return Err(ValkeyError::Str(utils::NOT_FOUND)),
+        Err(_) => return Err(ValkeyError::WrongType),
+    };
+    let num_chunks = bf.num_scandump_chunks();
+    if iterator >= num_chunks {
+        return Err(ValkeyError::Str(utils::INVALID_CHUNK));
+    }
+    let chunk = match bf.encode_scandump_chunk(iterator) {
+        Ok(bytes) => bytes,
+        Err(err) => return Err(ValkeyError::Str(err.as_str())),
+    };
+    let next_iterator = if iterator + 1 == num_chunks {
+        0
+    } else {
+        iterator + 1
+    };
+    Ok(ValkeyValue::Array(vec![
+        ValkeyValue::Integer(next_iterator as i64),
+        ValkeyValue::BulkString(chunk),
+    ]))
+}
+
+/// Function that implements logic to handle the BF.LOADCHUNK command.
+/// `BF.LOADCHUNK <key> <iterator> <data>` feeds back a chunk produced by `BF.SCANDUMP`, accumulating
+/// sub-filters in `LOADCHUNK_TRANSFERS` until the header's declared `num_filters` have all arrived, at
+/// which point the object is installed under `key` in a single step. `key` must not already exist, and
+/// chunks for a given transfer must arrive in order starting from iterator `0`.
+pub fn bloom_filter_loadchunk(ctx: &Context, input_args: &[ValkeyString]) -> ValkeyResult {
+    let argc = input_args.len();
+    if argc != 4 {
+        return Err(ValkeyError::WrongArity);
+    }
+    let filter_name = &input_args[1];
+    let iterator = match input_args[2].to_string_lossy().parse::<usize>() {
+        Ok(num) => num,
+        Err(_) => return Err(ValkeyError::Str(utils::INVALID_CHUNK)),
+    };
+    let data = input_args[3].as_slice();
+    let mut transfers = LOADCHUNK_TRANSFERS
+        .lock()
+        .expect("Unable to get a lock on the BF.LOADCHUNK transfer table");
+    let transfer_key = filter_name.as_slice().to_vec();
+
+    if iterator == 0 {
+        let filter_key = ctx.open_key(filter_name);
+        match filter_key.get_value::<BloomObject>(&BLOOM_TYPE) {
+            Ok(Some(_)) => return Err(ValkeyError::Str(utils::KEY_EXISTS)),
+            Ok(None) => {}
+            Err(_) => return Err(ValkeyError::WrongType),
+        }
+        let (expansion, fp_rate, tightening_ratio, is_seed_random, num_filters, counting_bits) =
+            match BloomObject::decode_scandump_header(data) {
+                Ok(header) => header,
+                Err(err) => return Err(ValkeyError::Str(err.as_str())),
+            };
+        // Bound the header's declared sub-filter count the same way a normal scale-out is bounded, so a
+        // corrupt or adversarial chunk-0 payload can't force an oversized `Vec::with_capacity` allocation
+        // before a single sub-filter chunk has actually been validated.
+        if num_filters >= configs::BLOOM_NUM_FILTERS_PER_OBJECT_LIMIT_MAX as usize {
+            return Err(ValkeyError::Str(utils::INVALID_CHUNK));
+        }
+        transfers.insert(
+            transfer_key,
+            PendingLoadChunk {
+                expansion,
+                fp_rate,
+                tightening_ratio,
+                is_seed_random,
+                num_filters,
+                filters: Vec::with_capacity(num_filters),
+                counting_bits,
+            },
+        );
+        ctx.replicate_verbatim();
+        return VALKEY_OK;
+    }
+
+    let pending = match transfers.get_mut(&transfer_key) {
+        Some(p) => p,
+        None => return Err(ValkeyError::Str(utils::INVALID_CHUNK)),
+    };
+    if iterator != pending.filters.len() + 1 {
+        transfers.remove(&transfer_key);
+        return Err(ValkeyError::Str(utils::INVALID_CHUNK));
+    }
+    let filter = match BloomObject::decode_scandump_filter_chunk(data) {
+        Ok(f) => f,
+        Err(err) => {
+            transfers.remove(&transfer_key);
+            return Err(ValkeyError::Str(err.as_str()));
+        }
+    };
+    pending.filters.push(filter);
+    if pending.filters.len() < pending.num_filters {
+        ctx.replicate_verbatim();
+        return VALKEY_OK;
+    }
+
+    let pending = transfers
+        .remove(&transfer_key)
+        .expect("transfer was just looked up above");
+    let validate_size_limit = !ctx.get_flags().contains(ContextFlags::REPLICATED);
+    let bloom = match BloomObject::from_existing(
+        pending.expansion,
+        pending.fp_rate,
+        pending.tightening_ratio,
+        pending.is_seed_random,
+        pending.filters,
+        pending.counting_bits,
+        validate_size_limit,
+    ) {
+        Ok(bf) => bf,
+        Err(err) => {
+            drop(transfers);
+            return Err(ValkeyError::Str(err.as_str()));
+        }
+    };
+    drop(transfers);
+    let filter_key = ctx.open_key_writable(filter_name);
+    match filter_key.get_value::<BloomObject>(&BLOOM_TYPE) {
+        Ok(Some(_)) => return Err(ValkeyError::Str(utils::KEY_EXISTS)),
+        Ok(None) => {}
+        Err(_) => return Err(ValkeyError::WrongType),
+    }
+    match filter_key.set_value(&BLOOM_TYPE, bloom) {
+        Ok(_) => {
+            ctx.replicate_verbatim();
+            ctx.notify_keyspace_event(NotifyEvent::GENERIC, utils::RESERVE_EVENT, filter_name);
+            VALKEY_OK
+        }
+        Err(_) => Err(ValkeyError::Str(utils::ERROR)),
+    }
+}
+
+/// Function that implements logic to handle the BF.CASCADE.BUILD command.
+/// `BF.CASCADE.BUILD <key> <fp_rate> INCLUDE <item> [<item> ...] EXCLUDE <item> [<item> ...] [SEEDS <seed>
+/// [<seed> ...]]` builds a `BloomCascade` encoding exact membership in the `INCLUDE` set against the
+/// disjoint `EXCLUDE` set. See `bloom::cascade::BloomCascade::build`.
+///
+/// `SEEDS` mirrors `BF.INSERT`'s `SEED` option: it is only supplied on replicated/AOF-replayed commands,
+/// carrying the exact per-level seeds the primary's `BloomCascade::build` drew, so the replica rebuilds a
+/// bit-identical cascade via `BloomCascade::build_with_seeds` instead of drawing its own randomness (which
+/// would silently diverge from the primary). On the primary, the seeds are drawn fresh and this command is
+/// re-emitted to replicas/AOF with an explicit `SEEDS` section appended.
+pub fn bloom_cascade_build(ctx: &Context, input_args: &[ValkeyString]) -> ValkeyResult {
+    let argc = input_args.len();
+    if argc < 6 {
+        return Err(ValkeyError::WrongArity);
+    }
+    let filter_name = &input_args[1];
+    let fp_rate = match input_args[2].to_string_lossy().parse::<f64>() {
+        Ok(rate) if rate > BLOOM_FP_RATE_MIN && rate < BLOOM_FP_RATE_MAX => rate,
+        _ => return Err(ValkeyError::Str(utils::ERROR_RATE_RANGE)),
+    };
+    if input_args[3].to_string_lossy().to_uppercase() != "INCLUDE" {
+        return Err(ValkeyError::Str(utils::UNKNOWN_ARGUMENT));
+    }
+    let mut idx = 4;
+    let mut included: Vec<Vec<u8>> = Vec::new();
+    while idx < argc && input_args[idx].to_string_lossy().to_uppercase() != "EXCLUDE" {
+        included.push(input_args[idx].as_slice().to_vec());
+        idx += 1;
+    }
+    if idx >= argc || included.is_empty() {
+        return Err(ValkeyError::Str(utils::UNKNOWN_ARGUMENT));
+    }
+    idx += 1;
+    let mut excluded: Vec<Vec<u8>> = Vec::new();
+    while idx < argc && input_args[idx].to_string_lossy().to_uppercase() != "SEEDS" {
+        excluded.push(input_args[idx].as_slice().to_vec());
+        idx += 1;
+    }
+    if excluded.is_empty() {
+        return Err(ValkeyError::Str(utils::UNKNOWN_ARGUMENT));
+    }
+    // `SEEDS` is only ever present on a replicated/AOF-replayed invocation (see doc comment above); a
+    // direct user call never supplies it and takes the random-seed path below.
+    let replicated_seeds: Option<Vec<[u8; 32]>> = if idx < argc {
+        idx += 1;
+        let mut seeds = Vec::with_capacity(argc - idx);
+        for arg in &input_args[idx..] {
+            let seed: Result<[u8; 32], _> = arg.as_slice().try_into();
+            match seed {
+                Ok(seed) => seeds.push(seed),
+                Err(_) => return Err(ValkeyError::Str(utils::INVALID_SEED)),
+            }
+        }
+        Some(seeds)
+    } else {
+        None
+    };
+
+    let filter_key = ctx.open_key_writable(filter_name);
+    match filter_key.get_value::<BloomCascade>(&BLOOM_CASCADE_TYPE) {
+        Ok(Some(_)) => return Err(ValkeyError::Str(utils::KEY_EXISTS)),
+        Ok(None) => {}
+        Err(_) => return Err(ValkeyError::WrongType),
+    }
+    let (cascade, seeds_used) = match replicated_seeds {
+        Some(seeds) => {
+            let cascade = BloomCascade::build_with_seeds(&included, &excluded, fp_rate, &seeds)
+                .map_err(|err| ValkeyError::Str(err.as_str()))?;
+            (cascade, seeds)
+        }
+        None => BloomCascade::build(&included, &excluded, fp_rate),
+    };
+    match filter_key.set_value(&BLOOM_CASCADE_TYPE, cascade) {
+        Ok(_) => {
+            // Deterministic replication, mirroring `replicate_and_notify_events`'s RESERVE path: re-emit
+            // this command with the per-level seeds actually used appended as an explicit `SEEDS` section,
+            // instead of `ctx.replicate_verbatim()`, so a replica reconstructs the identical cascade rather
+            // than drawing its own random seeds.
+            let seeds_str = ValkeyString::create_from_slice(std::ptr::null_mut(), "SEEDS".as_bytes());
+            let seed_vals: Vec<ValkeyString> = seeds_used
+                .iter()
+                .map(|seed| ValkeyString::create_from_slice(std::ptr::null_mut(), seed))
+                .collect();
+            let mut cmd: Vec<&ValkeyString> = input_args[1..].iter().collect();
+            cmd.push(&seeds_str);
+            for seed_val in &seed_vals {
+                cmd.push(seed_val);
+            }
+            ctx.replicate("BF.CASCADE.BUILD", cmd.as_slice());
+            ctx.notify_keyspace_event(NotifyEvent::GENERIC, utils::RESERVE_EVENT, filter_name);
+            VALKEY_OK
+        }
+        Err(_) => Err(ValkeyError::Str(utils::ERROR)),
+    }
+}
+
+/// Function that implements logic to handle the BF.CASCADE.QUERY command.
+/// `BF.CASCADE.QUERY <key> <item> [<item> ...]` reports, with zero false positives, whether each item is a
+/// member of the cascade's `INCLUDE` set.
+pub fn bloom_cascade_query(ctx: &Context, input_args: &[ValkeyString]) -> ValkeyResult {
+    let argc = input_args.len();
+    if argc < 3 {
+        return Err(ValkeyError::WrongArity);
+    }
+    let filter_name = &input_args[1];
+    let filter_key = ctx.open_key(filter_name);
+    let cascade = match filter_key.get_value::<BloomCascade>(&BLOOM_CASCADE_TYPE) {
+        Ok(Some(v)) => v,
+        Ok(None) => return Err(ValkeyError::Str(utils::NOT_FOUND)),
+        Err(_) => return Err(ValkeyError::WrongType),
+    };
+    let result = input_args[2..]
+        .iter()
+        .map(|item| ValkeyValue::Integer(cascade.query(item.as_slice()) as i64))
+        .collect();
+    Ok(ValkeyValue::Array(result))
+}
+
+/// Function that implements logic to handle the BF.CASCADE.INFO command.
+/// `BF.CASCADE.INFO <key>` reports the number of levels and the bit size of every level, mirroring
+/// `bloom_filter_info`'s shape for `BloomObject`.
+pub fn bloom_cascade_info(ctx: &Context, input_args: &[ValkeyString]) -> ValkeyResult {
+    if input_args.len() != 2 {
+        return Err(ValkeyError::WrongArity);
+    }
+    let filter_name = &input_args[1];
+    let filter_key = ctx.open_key(filter_name);
+    let cascade = match filter_key.get_value::<BloomCascade>(&BLOOM_CASCADE_TYPE) {
+        Ok(Some(v)) => v,
+        Ok(None) => return Err(ValkeyError::Str(utils::NOT_FOUND)),
+        Err(_) => return Err(ValkeyError::WrongType),
+    };
+    let level_sizes = cascade
+        .level_sizes()
+        .into_iter()
+        .map(|bits| ValkeyValue::Integer(bits as i64))
+        .collect();
+    Ok(ValkeyValue::Array(vec![
+        ValkeyValue::SimpleStringStatic("Number of levels"),
+        ValkeyValue::Integer(cascade.num_levels() as i64),
+        ValkeyValue::SimpleStringStatic("FP rate"),
+        ValkeyValue::BulkString(cascade.fp_rate().to_string().into_bytes()),
+        ValkeyValue::SimpleStringStatic("Level sizes (bits)"),
+        ValkeyValue::Array(level_sizes),
+    ]))
+}
+
+/// Function that implements logic to handle the BF.CASCADE.LOAD command.
+/// `BF.CASCADE.LOAD <key> <data>` restores a cascade previously serialized by `BloomCascade::encode_cascade`
+/// (e.g. via `cascade_aof_rewrite`). Mirrors `bloom_filter_load`'s pattern for `BloomObject`; `key` must not
+/// already exist.
+pub fn bloom_cascade_load(ctx: &Context, input_args: &[ValkeyString]) -> ValkeyResult {
+    let argc = input_args.len();
+    if argc != 3 {
+        return Err(ValkeyError::WrongArity);
+    }
+    let filter_name = &input_args[1];
+    let data = input_args[2].as_slice();
+    let filter_key = ctx.open_key_writable(filter_name);
+    match filter_key.get_value::<BloomCascade>(&BLOOM_CASCADE_TYPE) {
+        Ok(Some(_)) => return Err(ValkeyError::Str(utils::KEY_EXISTS)),
+        Ok(None) => {}
+        Err(_) => return Err(ValkeyError::WrongType),
+    }
+    let cascade = match BloomCascade::decode_cascade(data) {
+        Ok(v) => v,
+        Err(err) => return Err(ValkeyError::Str(err.as_str())),
+    };
+    match filter_key.set_value(&BLOOM_CASCADE_TYPE, cascade) {
+        Ok(_) => {
+            ctx.replicate_verbatim();
+            ctx.notify_keyspace_event(NotifyEvent::GENERIC, utils::RESERVE_EVENT, filter_name);
+            VALKEY_OK
+        }
+        Err(_) => Err(ValkeyError::Str(utils::ERROR)),
+    }
+}
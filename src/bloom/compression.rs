@@ -0,0 +1,85 @@
+use crate::bloom::utils::BloomError;
+
+/// Codec used to compress a single serialized sub-filter chunk (`BF.SCANDUMP`/`BF.LOADCHUNK` and the
+/// on-disk RDB representation share this format). Mirrors the block-compression approach used for filter
+/// blocks in LSM/SSTable storage: every chunk is prefixed with a one-byte codec tag and the original
+/// (uncompressed) length, so a reader can decompress unambiguously regardless of which codec produced it -
+/// this is what lets mixed-version clusters exchange filters across a `bloom-bitmap-compression` change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitmapCodec {
+    None,
+    Snappy,
+    Lz4,
+}
+
+impl BitmapCodec {
+    fn tag(self) -> u8 {
+        match self {
+            BitmapCodec::None => 0,
+            BitmapCodec::Snappy => 1,
+            BitmapCodec::Lz4 => 2,
+        }
+    }
+
+    fn from_tag(tag: u8) -> Result<BitmapCodec, BloomError> {
+        match tag {
+            0 => Ok(BitmapCodec::None),
+            1 => Ok(BitmapCodec::Snappy),
+            2 => Ok(BitmapCodec::Lz4),
+            _ => Err(BloomError::DecodeBloomFilterFailed),
+        }
+    }
+
+    /// Parses the `bloom-bitmap-compression` config value (`none` / `snappy` / `lz4`).
+    pub fn from_config_str(value: &str) -> Result<BitmapCodec, BloomError> {
+        match value {
+            "none" => Ok(BitmapCodec::None),
+            "snappy" => Ok(BitmapCodec::Snappy),
+            "lz4" => Ok(BitmapCodec::Lz4),
+            _ => Err(BloomError::BadBitmapCompression),
+        }
+    }
+}
+
+/// Compresses `bytes` with `codec` and prepends a one-byte codec tag plus the 4-byte (little-endian)
+/// original length. Falls back to storing `bytes` uncompressed (tag `None`) whenever the requested codec
+/// is `None`, or the compressed form would not actually be smaller than the input.
+pub fn compress(bytes: &[u8], codec: BitmapCodec) -> Vec<u8> {
+    let compressed = match codec {
+        BitmapCodec::None => None,
+        BitmapCodec::Snappy => snap::raw::Encoder::new().compress_vec(bytes).ok(),
+        BitmapCodec::Lz4 => Some(lz4_flex::compress(bytes)),
+    };
+    let (tag, body) = match compressed {
+        Some(body) if body.len() < bytes.len() => (codec.tag(), body),
+        _ => (BitmapCodec::None.tag(), bytes.to_vec()),
+    };
+    let mut out = Vec::with_capacity(body.len() + 5);
+    out.push(tag);
+    out.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+    out.extend_from_slice(&body);
+    out
+}
+
+/// Reverses [`compress`]: reads the codec tag and original length, then decompresses (or returns the body
+/// unchanged for the `None` tag).
+pub fn decompress(bytes: &[u8]) -> Result<Vec<u8>, BloomError> {
+    if bytes.len() < 5 {
+        return Err(BloomError::DecodeBloomFilterFailed);
+    }
+    let codec = BitmapCodec::from_tag(bytes[0])?;
+    let original_len = u32::from_le_bytes(
+        bytes[1..5]
+            .try_into()
+            .expect("slice of length 4 always converts"),
+    ) as usize;
+    let body = &bytes[5..];
+    match codec {
+        BitmapCodec::None => Ok(body.to_vec()),
+        BitmapCodec::Snappy => snap::raw::Decoder::new()
+            .decompress_vec(body)
+            .map_err(|_| BloomError::DecodeBloomFilterFailed),
+        BitmapCodec::Lz4 => lz4_flex::decompress(body, original_len)
+            .map_err(|_| BloomError::DecodeBloomFilterFailed),
+    }
+}
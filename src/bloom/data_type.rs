@@ -1,18 +1,87 @@
+use super::compression;
 use crate::bloom::utils::BloomFilter;
 use crate::bloom::utils::BloomFilterType;
 use crate::configs;
 use crate::wrapper::bloom_callback;
 use crate::wrapper::digest::Digest;
 use crate::MODULE_NAME;
-use std::os::raw::c_int;
+use lazy_static::lazy_static;
+use std::os::raw::{c_char, c_int};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
 use valkey_module::native_types::ValkeyType;
 use valkey_module::{logging, raw};
 
+const BLOOM_CASCADE_TYPE_ENCODING_VERSION: i32 = 1;
+
+/// Native data type backing `BF.CASCADE.*`. Kept separate from `BLOOM_FILTER_TYPE` since a `BloomCascade`
+/// is a different Rust struct (a flat stack of levels, not a scaling set of sub-filters) with its own RDB
+/// representation - see `bloom_callback::cascade_rdb_save`/`cascade_rdb_load`.
+pub static BLOOM_CASCADE_TYPE: ValkeyType = ValkeyType::new(
+    "bloomcasc",
+    BLOOM_CASCADE_TYPE_ENCODING_VERSION,
+    raw::RedisModuleTypeMethods {
+        version: raw::REDISMODULE_TYPE_METHOD_VERSION as u64,
+        rdb_load: Some(bloom_callback::cascade_rdb_load),
+        rdb_save: Some(bloom_callback::cascade_rdb_save),
+        aof_rewrite: Some(bloom_callback::cascade_aof_rewrite),
+        digest: None,
+
+        mem_usage: Some(bloom_callback::cascade_mem_usage),
+        free: Some(bloom_callback::cascade_free),
+
+        aux_load: None,
+        aux_save: None,
+        aux_save2: None,
+        aux_save_triggers: raw::Aux::Before as i32,
+
+        free_effort: None,
+        unlink: None,
+        copy: Some(bloom_callback::cascade_copy),
+        defrag: None,
+
+        mem_usage2: None,
+        free_effort2: None,
+        unlink2: None,
+        copy2: None,
+    },
+);
+
 /// Used for decoding and encoding `BloomFilterType`. Currently used in AOF Rewrite.
 /// This value must increased when `BloomFilterType` struct change.
 pub const BLOOM_TYPE_VERSION: u8 = 1;
 
-const BLOOM_FILTER_TYPE_ENCODING_VERSION: i32 = 1;
+/// Format tag `BloomObject::encode_object`/`decode_object` use for the standard, uncompressed
+/// scaling-filter payload: a plain `bincode::serialize` of the whole `BloomObject`.
+pub const BLOOM_OBJECT_VERSION: u8 = 1;
+
+/// Format tag `BloomObject::encode_object`/`decode_object` use to recognize a payload as an SBBF-backed
+/// object rather than the standard scaling-filter format tagged `BLOOM_OBJECT_VERSION`. Distinct from that
+/// constant because the two formats aren't versions of the same layout - an SBBF payload's body is the raw
+/// Apache Parquet Split Block Bloom Filter wire format, not a bincode-serialized `BloomObject`.
+pub const BLOOM_OBJECT_SBBF_VERSION: u8 = 2;
+
+/// Format tag for the same `bincode`-serialized `BloomObject` payload as `BLOOM_OBJECT_VERSION`, except the
+/// body has additionally been run through `compression::compress` - i.e. it's prefixed with that function's
+/// own codec tag + original-length header before the (possibly unchanged) bincode bytes. Kept as a distinct
+/// version rather than a flag inside the payload so an older reader that only understands
+/// `BLOOM_OBJECT_VERSION` fails loudly on `DecodeUnsupportedVersion` instead of misparsing compressed bytes
+/// as bincode. See `BloomObject::encode_object`/`decode_object`.
+pub const BLOOM_OBJECT_COMPRESSED_VERSION: u8 = 3;
+
+/// Oldest `bloomfltr` RDB/AOF encoding version `load_from_rdb` still has an explicit reader for. Only
+/// move this forward when that reader is actually deleted - it is what lets a newer module load an
+/// older one's data during a rolling upgrade.
+pub(crate) const BLOOM_FILTER_TYPE_MIN_ENCODING_VERSION: i32 = 1;
+
+/// Current/max `bloomfltr` encoding version this module writes and will accept. Version 2 adds a
+/// length-prefixed, currently-empty extension blob after each sub-filter and once more after the last one
+/// so a later version can append fields there that this reader (or an even older one once it catches
+/// up to v2) can skip instead of aborting. Version 3 additionally runs each sub-filter's saved bitmap
+/// through `compression::compress` (driven by `bloom-bitmap-compression`, same codec `encode_object`
+/// uses) before writing it, rather than the raw bytes versions 1 and 2 wrote verbatim. See
+/// `load_from_rdb`'s per-version readers.
+pub(crate) const BLOOM_FILTER_TYPE_ENCODING_VERSION: i32 = 3;
 
 pub static BLOOM_FILTER_TYPE: ValkeyType = ValkeyType::new(
     "bloomfltr",
@@ -28,9 +97,10 @@ pub static BLOOM_FILTER_TYPE: ValkeyType = ValkeyType::new(
         free: Some(bloom_callback::bloom_free),
 
         aux_load: Some(bloom_callback::bloom_aux_load),
-        // Callback not needed as there is no AUX (out of keyspace) data to be saved.
-        aux_save: None,
-        aux_save2: None,
+        // Writes/reads the module-level compatibility manifest consumed by `bloom_rdb_aux_load` so a
+        // rolling upgrade/downgrade can negotiate instead of hard-failing on an unrecognized `encver`.
+        aux_save: Some(bloom_callback::bloom_aux_save),
+        aux_save2: Some(bloom_callback::bloom_aux_save),
         aux_save_triggers: raw::Aux::Before as i32,
 
         free_effort: Some(bloom_callback::bloom_free_effort),
@@ -52,13 +122,82 @@ pub trait ValkeyDataType {
     fn debug_digest(&self, dig: Digest);
 }
 
+/// Set the first time in a given RDB/AOF load that `load_from_rdb` takes a compatibility path (an
+/// `encver` below our current version), so operators get one warning per load instead of one per key.
+/// Reset by `bloom_rdb_aux_load`, which runs once before any per-key data thanks to
+/// `aux_save_triggers: raw::Aux::Before`.
+static COMPAT_WARNED_THIS_LOAD: AtomicBool = AtomicBool::new(false);
+
+fn warn_compat_path_once(encver: i32) {
+    if !COMPAT_WARNED_THIS_LOAD.swap(true, Ordering::Relaxed) {
+        logging::log_warning(format!("{}: Loading bloomfltr data using the version {} compatibility reader (module's current version is {}). This is expected during a rolling upgrade/downgrade.", MODULE_NAME, encver, BLOOM_FILTER_TYPE_ENCODING_VERSION).as_str());
+    }
+}
+
+/// Reads and discards a version-2-and-later per-record extension blob: a length-prefixed byte string
+/// reserved for fields a later encoding version might add here. We don't understand its contents, so
+/// skipping it wholesale is how this reader stays able to load data from a module that is a little bit
+/// newer than `BLOOM_FILTER_TYPE_ENCODING_VERSION` without aborting.
+fn skip_extension_blob(rdb: *mut raw::RedisModuleIO) -> Option<()> {
+    raw::load_string_buffer(rdb).ok().map(|_| ())
+}
+
 impl ValkeyDataType for BloomFilterType {
-    /// Callback to load and parse RDB data of a bloom item and create it.
+    /// Callback to load and parse RDB data of a bloom item and create it. Dispatches to an explicit
+    /// per-version reader so a newer module stays able to load an older one's data, and a module that
+    /// is only slightly behind `encver` can skip unknown trailing fields instead of refusing to load.
     fn load_from_rdb(rdb: *mut raw::RedisModuleIO, encver: i32) -> Option<BloomFilterType> {
-        if encver > BLOOM_FILTER_TYPE_ENCODING_VERSION {
-            logging::log_warning(format!("{}: Cannot load bloomfltr data type of version {} because it is higher than the loaded module's bloomfltr supported version {}", MODULE_NAME, encver, BLOOM_FILTER_TYPE_ENCODING_VERSION).as_str());
+        if !(BLOOM_FILTER_TYPE_MIN_ENCODING_VERSION..=BLOOM_FILTER_TYPE_ENCODING_VERSION)
+            .contains(&encver)
+        {
+            logging::log_warning(format!("{}: Cannot load bloomfltr data type of version {} because it is outside the loaded module's supported range [{}, {}]", MODULE_NAME, encver, BLOOM_FILTER_TYPE_MIN_ENCODING_VERSION, BLOOM_FILTER_TYPE_ENCODING_VERSION).as_str());
             return None;
         }
+        if encver < BLOOM_FILTER_TYPE_ENCODING_VERSION {
+            warn_compat_path_once(encver);
+        }
+        match encver {
+            3 => Self::load_from_rdb_v3(rdb),
+            2 => Self::load_from_rdb_v2(rdb),
+            _ => Self::load_from_rdb_v1(rdb, false, false),
+        }
+    }
+
+    /// Function that is used to generate a digest on the Bloom Object.
+    fn debug_digest(&self, mut dig: Digest) {
+        dig.add_long_long(self.expansion() as i64);
+        dig.add_string_buffer(&self.fp_rate().to_le_bytes());
+        dig.add_string_buffer(&self.tightening_ratio().to_le_bytes());
+        let is_seed_random = if self.is_seed_random() { 1 } else { 0 };
+        dig.add_long_long(is_seed_random);
+        for filter in self.filters() {
+            dig.add_string_buffer(filter.raw_bloom().as_slice());
+            dig.add_long_long(filter.num_items());
+            dig.add_long_long(filter.capacity());
+            // `m`, the bitmap's bit length, drives the rejection-sampled hash-to-index mapping in
+            // `BloomFilter::hash_indices`; digest it explicitly rather than relying on callers to
+            // re-derive it from the raw bitmap's byte length, so it stays stable across encodings.
+            dig.add_long_long(filter.raw_bloom().len() as i64);
+        }
+        dig.end_sequence();
+    }
+}
+
+impl BloomFilterType {
+    /// Version 1 reader: no per-record or per-object extension blobs, bitmaps stored raw. With both flags
+    /// below `false` it reads payloads written before version 2 introduced those blobs, for as long as
+    /// `BLOOM_FILTER_TYPE_MIN_ENCODING_VERSION <= 1`; it is also the shared per-record reader that
+    /// `load_from_rdb_v2`/`v3` build on.
+    ///
+    /// `has_extension_blobs` is `true` when called from `load_from_rdb_v2`/`v3` for the shared per-record
+    /// layout, since versions 1 and 2 only differ in whether those blobs are present. `bitmaps_compressed`
+    /// is `true` when called from `load_from_rdb_v3`, whose saved bitmaps went through
+    /// `compression::compress` rather than being written raw.
+    fn load_from_rdb_v1(
+        rdb: *mut raw::RedisModuleIO,
+        has_extension_blobs: bool,
+        bitmaps_compressed: bool,
+    ) -> Option<BloomFilterType> {
         let Ok(num_filters) = raw::load_unsigned(rdb) else {
             return None;
         };
@@ -81,9 +220,22 @@ impl ValkeyDataType for BloomFilterType {
         let mut filters = Vec::with_capacity(1);
 
         for i in 0..num_filters {
-            let Ok(bitmap) = raw::load_string_buffer(rdb) else {
+            let Ok(saved_bitmap) = raw::load_string_buffer(rdb) else {
                 return None;
             };
+            let bitmap = if bitmaps_compressed {
+                match compression::decompress(saved_bitmap.as_ref()) {
+                    Ok(bitmap) => bitmap,
+                    Err(_) => {
+                        logging::log_warning(
+                            "Failed to restore bloom object: Could not decompress saved bitmap.",
+                        );
+                        return None;
+                    }
+                }
+            } else {
+                saved_bitmap.as_ref().to_vec()
+            };
             let Ok(capacity) = raw::load_unsigned(rdb) else {
                 return None;
             };
@@ -110,12 +262,16 @@ impl ValkeyDataType for BloomFilterType {
             } else {
                 capacity
             };
-            let filter =
-                BloomFilter::from_existing(bitmap.as_ref(), num_items as i64, capacity as i64);
+            let filter = BloomFilter::from_existing(&bitmap, num_items as i64, capacity as i64);
             if !is_seed_random && filter.seed() != configs::FIXED_SEED {
                 logging::log_warning("Failed to restore bloom object: Object in fixed seed mode, but seed does not match FIXED_SEED.");
                 return None;
             }
+            // Version 2 and later carry a per-filter extension blob we don't have fields for yet;
+            // skip it rather than leaving it unread and desyncing the rest of the RDB stream.
+            if has_extension_blobs && skip_extension_blob(rdb).is_none() {
+                return None;
+            }
             filters.push(Box::new(filter));
         }
         let item = BloomFilterType::from_existing(
@@ -128,24 +284,121 @@ impl ValkeyDataType for BloomFilterType {
         Some(item)
     }
 
-    /// Function that is used to generate a digest on the Bloom Object.
-    fn debug_digest(&self, mut dig: Digest) {
-        dig.add_long_long(self.expansion() as i64);
-        dig.add_string_buffer(&self.fp_rate().to_le_bytes());
-        dig.add_string_buffer(&self.tightening_ratio().to_le_bytes());
-        let is_seed_random = if self.is_seed_random() { 1 } else { 0 };
-        dig.add_long_long(is_seed_random);
-        for filter in self.filters() {
-            dig.add_string_buffer(filter.raw_bloom().as_slice());
-            dig.add_long_long(filter.num_items());
-            dig.add_long_long(filter.capacity());
-        }
-        dig.end_sequence();
+    /// Version 2 reader: identical wire layout to version 1, plus a per-filter extension blob (handled
+    /// by `load_from_rdb_v1` when `has_extension_blobs` is set) and a trailing object-level extension
+    /// blob. Both are currently always empty - they exist so a future version can add fields without
+    /// forcing every reader of version 2 data to be rewritten, only extended to parse what it finds
+    /// inside the blob.
+    fn load_from_rdb_v2(rdb: *mut raw::RedisModuleIO) -> Option<BloomFilterType> {
+        let item = Self::load_from_rdb_v1(rdb, true, false)?;
+        skip_extension_blob(rdb)?;
+        Some(item)
+    }
+
+    /// Version 3 reader: identical wire layout to version 2, except each sub-filter's saved bitmap was
+    /// written through `compression::compress` (see `bloom_callback::bloom_rdb_save`) rather than raw, so
+    /// it must be run through `compression::decompress` before being handed to `BloomFilter::from_existing`.
+    fn load_from_rdb_v3(rdb: *mut raw::RedisModuleIO) -> Option<BloomFilterType> {
+        let item = Self::load_from_rdb_v1(rdb, true, true)?;
+        skip_extension_blob(rdb)?;
+        Some(item)
     }
 }
 
-/// Load the auxiliary data outside of the regular keyspace from the RDB file
-pub fn bloom_rdb_aux_load(_rdb: *mut raw::RedisModuleIO) -> c_int {
-    logging::log_notice("Ignoring AUX fields during RDB load.");
+/// What the aux manifest from the RDB/AOF currently being loaded told us about the module that wrote
+/// it. `None` until a manifest has actually been parsed - e.g. a fresh process that hasn't loaded
+/// anything yet, or a payload saved by a module old enough to predate `aux_save`.
+#[derive(Clone, Copy, Debug)]
+pub struct RdbCompatManifest {
+    pub peer_min_encver: i32,
+    pub peer_max_encver: i32,
+    pub peer_algorithms: u32,
+}
+
+/// Bits set in the aux manifest's "enabled algorithms" field, one per backend this binary can
+/// construct or decode. Always all-set today since every backend is compiled in unconditionally;
+/// exists so a peer missing one (or a future peer with one we don't have) shows up as a specific,
+/// named gap instead of a generic version mismatch.
+pub const BLOOM_ALGO_STANDARD: u32 = 1 << 0;
+pub const BLOOM_ALGO_COUNTING: u32 = 1 << 1;
+pub const BLOOM_ALGO_SBBF: u32 = 1 << 2;
+pub const BLOOM_ALGO_MURMUR128: u32 = 1 << 3;
+pub const BLOOM_ALGO_RIBBON: u32 = 1 << 4;
+const BLOOM_ALGO_ALL: u32 = BLOOM_ALGO_STANDARD
+    | BLOOM_ALGO_COUNTING
+    | BLOOM_ALGO_SBBF
+    | BLOOM_ALGO_MURMUR128
+    | BLOOM_ALGO_RIBBON;
+
+lazy_static! {
+    static ref LAST_LOADED_MANIFEST: Mutex<Option<RdbCompatManifest>> = Mutex::new(None);
+}
+
+/// Returns the manifest parsed from the most recently loaded RDB/AOF, if any. Used to surface whether
+/// a downgrade to an older module would be safe (e.g. via `BF.INFO`/module `INFO` output) instead of
+/// operators having to find out by trying it.
+pub fn last_loaded_rdb_compat_manifest() -> Option<RdbCompatManifest> {
+    *LAST_LOADED_MANIFEST
+        .lock()
+        .expect("We expect the RDB compat manifest mutex to exist.")
+}
+
+/// Writes the module-level manifest `bloom_rdb_aux_load` parses on the other end: the range of
+/// `bloomfltr` encoding versions this module can load, the algorithm backends it supports, and the
+/// fixed seed filters created with `bloom-use-random-seed no` were hashed with. Lets a module loading
+/// this RDB negotiate compatibility instead of hard-failing on an unrecognized `encver`.
+pub fn bloom_rdb_aux_save(rdb: *mut raw::RedisModuleIO) {
+    raw::save_unsigned(rdb, BLOOM_FILTER_TYPE_MIN_ENCODING_VERSION as u64);
+    raw::save_unsigned(rdb, BLOOM_FILTER_TYPE_ENCODING_VERSION as u64);
+    raw::save_unsigned(rdb, BLOOM_ALGO_ALL as u64);
+    unsafe {
+        raw::RedisModule_SaveStringBuffer.unwrap()(
+            rdb,
+            configs::FIXED_SEED.as_ptr().cast::<c_char>(),
+            configs::FIXED_SEED.len(),
+        );
+    }
+}
+
+/// Load the auxiliary data outside of the regular keyspace from the RDB file: the compatibility
+/// manifest `bloom_rdb_aux_save` writes. Resets the per-load "already warned about a compatibility
+/// path" flag, since this runs once before any per-key data (`aux_save_triggers: raw::Aux::Before`).
+pub fn bloom_rdb_aux_load(rdb: *mut raw::RedisModuleIO) -> c_int {
+    COMPAT_WARNED_THIS_LOAD.store(false, Ordering::Relaxed);
+    let (Ok(peer_min_encver), Ok(peer_max_encver), Ok(peer_algorithms), Ok(peer_seed)) = (
+        raw::load_unsigned(rdb),
+        raw::load_unsigned(rdb),
+        raw::load_unsigned(rdb),
+        raw::load_string_buffer(rdb),
+    ) else {
+        logging::log_warning(
+            format!(
+                "{}: Failed to read the bloomfltr compatibility manifest from the RDB/AOF aux data.",
+                MODULE_NAME
+            )
+            .as_str(),
+        );
+        return raw::Status::Err as i32;
+    };
+    let manifest = RdbCompatManifest {
+        peer_min_encver: peer_min_encver as i32,
+        peer_max_encver: peer_max_encver as i32,
+        peer_algorithms: peer_algorithms as u32,
+    };
+    if manifest.peer_max_encver < BLOOM_FILTER_TYPE_MIN_ENCODING_VERSION
+        || manifest.peer_min_encver > BLOOM_FILTER_TYPE_ENCODING_VERSION
+    {
+        logging::log_warning(format!("{}: RDB/AOF was written by a module supporting bloomfltr versions [{}, {}], which does not overlap this module's supported range [{}, {}]. Keys of this type will fail to load.", MODULE_NAME, manifest.peer_min_encver, manifest.peer_max_encver, BLOOM_FILTER_TYPE_MIN_ENCODING_VERSION, BLOOM_FILTER_TYPE_ENCODING_VERSION).as_str());
+    }
+    let unknown_algorithms = manifest.peer_algorithms & !BLOOM_ALGO_ALL;
+    if unknown_algorithms != 0 {
+        logging::log_notice(format!("{}: RDB/AOF was written by a module supporting algorithm bits {:#x} that this module doesn't recognize; any key using one of those backends will fail to load.", MODULE_NAME, unknown_algorithms).as_str());
+    }
+    if peer_seed.as_ref() != &configs::FIXED_SEED[..] {
+        logging::log_notice(format!("{}: RDB/AOF's fixed seed differs from this module's FIXED_SEED; fixed-seed keys saved there won't match a fresh fixed-seed filter created here.", MODULE_NAME).as_str());
+    }
+    *LAST_LOADED_MANIFEST
+        .lock()
+        .expect("We expect the RDB compat manifest mutex to exist.") = Some(manifest);
     raw::Status::Ok as i32
 }
@@ -0,0 +1,115 @@
+use super::utils::BloomError;
+
+/// A single step that rewrites a `BloomObject::encode_object` (or other bloom wire-format) payload written
+/// by an older module version into the next version in the chain, without materializing the higher-level
+/// Rust type in between. `decode_object` chains registered migrations so a payload written by an older
+/// module during a rolling upgrade is transparently upgraded instead of hard-rejected with
+/// `BloomError::DecodeUnsupportedVersion`; a version newer than the running binary understands - for which
+/// no migration will ever be registered - still fails the same way it always has.
+pub trait LayoutMigration: Sync {
+    /// The version tag this migration reads.
+    fn from_version(&self) -> u8;
+    /// Rewrites `bytes` (its leading version-tag byte included) from `from_version()` to whatever version
+    /// comes next in the chain - either another registered migration's `from_version`, or a version
+    /// `decode_object` already has a native reader for.
+    fn upgrade(&self, bytes: &[u8]) -> Result<Vec<u8>, BloomError>;
+}
+
+/// Every migration this module currently knows how to run. Empty today: every wire-format version this
+/// module has ever emitted (`BLOOM_OBJECT_VERSION`, `BLOOM_OBJECT_SBBF_VERSION`,
+/// `BLOOM_OBJECT_COMPRESSED_VERSION`) still has a native reader in `decode_object`, so nothing has needed
+/// migrating away yet - but routing `decode_object` through this chain now means the next on-disk layout
+/// bump only needs a new entry here instead of a `decode_object` rewrite and a coordinated cluster restart.
+fn migrations() -> &'static [&'static dyn LayoutMigration] {
+    &[]
+}
+
+/// Rewrites `bytes` forward through `migrations` until its leading version tag satisfies `is_known`
+/// (including immediately, if it already does). Returns `BloomError::DecodeUnsupportedVersion` once no
+/// migration in the list recognizes the current tag - which covers both a version newer than this binary
+/// understands and one old enough that support for it has actually been dropped.
+fn upgrade_with(
+    bytes: &[u8],
+    is_known: impl Fn(u8) -> bool,
+    migrations: &[&dyn LayoutMigration],
+) -> Result<Vec<u8>, BloomError> {
+    let mut bytes = bytes.to_vec();
+    // Bounded by the chain length plus one: a well-formed registry visits each migration at most once, so
+    // this also catches a migration that doesn't actually advance the version (a registry bug) rather than
+    // looping forever.
+    for _ in 0..=migrations.len() {
+        let version = *bytes.first().ok_or(BloomError::DecodeBloomFilterFailed)?;
+        if is_known(version) {
+            return Ok(bytes);
+        }
+        let migration = migrations
+            .iter()
+            .find(|m| m.from_version() == version)
+            .ok_or(BloomError::DecodeUnsupportedVersion)?;
+        bytes = migration.upgrade(&bytes)?;
+    }
+    Err(BloomError::DecodeUnsupportedVersion)
+}
+
+/// Upgrades `bytes` through the live migration registry. See `upgrade_with`.
+pub fn upgrade_to_known(bytes: &[u8], is_known: impl Fn(u8) -> bool) -> Result<Vec<u8>, BloomError> {
+    upgrade_with(bytes, is_known, migrations())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct RenameVersion {
+        from: u8,
+        to: u8,
+    }
+
+    impl LayoutMigration for RenameVersion {
+        fn from_version(&self) -> u8 {
+            self.from
+        }
+
+        fn upgrade(&self, bytes: &[u8]) -> Result<Vec<u8>, BloomError> {
+            let mut out = vec![self.to];
+            out.extend_from_slice(&bytes[1..]);
+            Ok(out)
+        }
+    }
+
+    #[test]
+    fn test_upgrade_with_returns_input_unchanged_when_already_known() {
+        let bytes = vec![5, 1, 2, 3];
+        let result = upgrade_with(&bytes, |v| v == 5, &[]).unwrap();
+        assert_eq!(result, bytes);
+    }
+
+    #[test]
+    fn test_upgrade_with_chains_multiple_migrations_to_a_known_version() {
+        let step_a = RenameVersion { from: 1, to: 2 };
+        let step_b = RenameVersion { from: 2, to: 3 };
+        let migrations: Vec<&dyn LayoutMigration> = vec![&step_a, &step_b];
+        let bytes = vec![1, 0xaa, 0xbb];
+        let result = upgrade_with(&bytes, |v| v == 3, &migrations).unwrap();
+        assert_eq!(result, vec![3, 0xaa, 0xbb]);
+    }
+
+    #[test]
+    fn test_upgrade_with_rejects_a_version_with_no_registered_migration() {
+        let step_a = RenameVersion { from: 1, to: 2 };
+        let migrations: Vec<&dyn LayoutMigration> = vec![&step_a];
+        let bytes = vec![99, 0xaa];
+        assert_eq!(
+            upgrade_with(&bytes, |v| v == 2, &migrations).unwrap_err(),
+            BloomError::DecodeUnsupportedVersion
+        );
+    }
+
+    #[test]
+    fn test_upgrade_with_rejects_empty_bytes() {
+        assert_eq!(
+            upgrade_with(&[], |_| true, &[]).unwrap_err(),
+            BloomError::DecodeBloomFilterFailed
+        );
+    }
+}
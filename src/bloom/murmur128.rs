@@ -0,0 +1,173 @@
+use crate::bloom::utils::BloomError;
+use serde::{Deserialize, Serialize};
+
+/// A fixed-size (non-scaling) bloom filter hashed with 128-bit MurmurHash3 instead of the sip-hash based
+/// default (see `BloomFilter`). Rather than hashing an item `k` times, it derives all `num_hashes` bit
+/// positions from the two 64-bit halves of a single `murmur3::hash128_with_seed` call via the
+/// Kirsch-Mitzenmacher double-hashing scheme `g_i = (h1 + i*h2) mod m`. Selected with `BF.RESERVE ... HASH
+/// MURMUR128` so filters built or consumed by murmur3-based external tools (e.g. spellcheck-rs) round-trip
+/// correctly - see `BloomObject::new_reserved_murmur128`.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Murmur128Filter {
+    bits: Vec<u8>,
+    num_bits: u64,
+    num_hashes: u32,
+    seed: u32,
+    capacity: i64,
+    num_items: i64,
+}
+
+impl Murmur128Filter {
+    /// Sizes an empty filter for `capacity` items at false positive rate `fp_rate`, using the standard
+    /// bloom filter formulas for bit-array size and hash count (the same sizing every `HASH default` filter
+    /// targets, just hashed differently).
+    pub fn new_reserved(
+        capacity: i64,
+        fp_rate: f64,
+        seed: u32,
+    ) -> Result<Murmur128Filter, BloomError> {
+        if !(fp_rate > 0.0 && fp_rate < 1.0) {
+            return Err(BloomError::ErrorRateRange);
+        }
+        if capacity <= 0 {
+            return Err(BloomError::BadCapacity);
+        }
+        let ln2_sq = std::f64::consts::LN_2 * std::f64::consts::LN_2;
+        let num_bits = (-(capacity as f64) * fp_rate.ln() / ln2_sq).ceil() as u64;
+        let num_bits = num_bits.max(8);
+        let num_hashes = ((num_bits as f64 / capacity as f64) * std::f64::consts::LN_2)
+            .round()
+            .max(1.0) as u32;
+        Ok(Murmur128Filter {
+            bits: vec![0u8; num_bits.div_ceil(8) as usize],
+            num_bits,
+            num_hashes,
+            seed,
+            capacity,
+            num_items: 0,
+        })
+    }
+
+    /// Computes the `num_hashes` bit positions `item` maps to via `g_i = (h1 + i*h2) mod m`, where `h1`/
+    /// `h2` are the low/high 64-bit halves of a single 128-bit murmur3 hash of `item`.
+    fn indices(&self, item: &[u8]) -> Vec<u64> {
+        let hash = murmur3::hash128_with_seed(item, self.seed);
+        let h1 = hash as u64;
+        let h2 = (hash >> 64) as u64;
+        (0..self.num_hashes as u64)
+            .map(|i| h1.wrapping_add(i.wrapping_mul(h2)) % self.num_bits)
+            .collect()
+    }
+
+    fn set_bit(&mut self, idx: u64) {
+        self.bits[(idx / 8) as usize] |= 1 << (idx % 8);
+    }
+
+    fn get_bit(&self, idx: u64) -> bool {
+        self.bits[(idx / 8) as usize] & (1 << (idx % 8)) != 0
+    }
+
+    /// Sets the bits `item` hashes to. Does not track `num_items` or check for existence; the caller
+    /// (`BloomObject::add_item`) is responsible for both, mirroring `BloomFilter::set`.
+    pub fn set(&mut self, item: &[u8]) {
+        for idx in self.indices(item) {
+            self.set_bit(idx);
+        }
+    }
+
+    /// Tests whether every bit `item` hashes to is set.
+    pub fn check(&self, item: &[u8]) -> bool {
+        self.indices(item).into_iter().all(|idx| self.get_bit(idx))
+    }
+
+    pub fn capacity(&self) -> i64 {
+        self.capacity
+    }
+
+    pub fn num_items(&self) -> i64 {
+        self.num_items
+    }
+
+    pub fn incr_num_items(&mut self) {
+        self.num_items += 1;
+    }
+
+    pub fn seed(&self) -> u32 {
+        self.seed
+    }
+
+    pub fn num_hashes(&self) -> u32 {
+        self.num_hashes
+    }
+
+    pub fn bits_set(&self) -> u64 {
+        self.bits.iter().map(|byte| byte.count_ones() as u64).sum()
+    }
+
+    pub fn bits_total(&self) -> u64 {
+        self.num_bits
+    }
+
+    pub fn fill_ratio(&self) -> f64 {
+        if self.num_bits == 0 {
+            return 0.0;
+        }
+        self.bits_set() as f64 / self.num_bits as f64
+    }
+
+    /// Estimates the realized false-positive probability from the filter's current bit-fill, mirroring
+    /// `BloomFilter::current_error_rate`.
+    pub fn current_error_rate(&self) -> f64 {
+        self.fill_ratio().powi(self.num_hashes as i32)
+    }
+
+    pub fn number_of_bytes(&self) -> usize {
+        std::mem::size_of::<Murmur128Filter>() + self.bits.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_murmur128_insert_and_check() {
+        let mut filter = Murmur128Filter::new_reserved(1000, 0.01, 0).unwrap();
+        let present: Vec<Vec<u8>> = (0..1000).map(|i| format!("item-{i}").into_bytes()).collect();
+        for item in &present {
+            filter.set(item);
+        }
+        for item in &present {
+            assert!(filter.check(item));
+        }
+        let false_positives = (0..1000)
+            .map(|i| format!("absent-{i}").into_bytes())
+            .filter(|item| filter.check(item))
+            .count();
+        assert!(
+            false_positives < 50,
+            "false positive rate too high: {false_positives}/1000"
+        );
+    }
+
+    #[test]
+    fn test_murmur128_different_seeds_diverge() {
+        let mut a = Murmur128Filter::new_reserved(100, 0.01, 1).unwrap();
+        let b = Murmur128Filter::new_reserved(100, 0.01, 2).unwrap();
+        a.set(b"item-0");
+        assert!(a.check(b"item-0"));
+        assert_ne!(a.seed(), b.seed());
+    }
+
+    #[test]
+    fn test_murmur128_rejects_bad_sizing() {
+        assert_eq!(
+            Murmur128Filter::new_reserved(0, 0.01, 0).err(),
+            Some(BloomError::BadCapacity)
+        );
+        assert_eq!(
+            Murmur128Filter::new_reserved(100, 1.5, 0).err(),
+            Some(BloomError::ErrorRateRange)
+        );
+    }
+}
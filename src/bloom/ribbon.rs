@@ -0,0 +1,254 @@
+use crate::bloom::utils::BloomError;
+use serde::{Deserialize, Serialize};
+
+/// Width (in bits) of the coefficient vector `c` each key's equation is solved against. Must fit in a
+/// `u64` so the banding loop below can find/shift pivot bits with plain integer ops.
+const COEFF_BITS: u32 = 64;
+
+/// A Ribbon filter: a solved linear system over GF(2) that stores membership in roughly 30% less space
+/// than an equivalent-FP-rate Bloom filter by giving up the ability to insert after construction. Selected
+/// with `bloom-filter-algorithm ribbon` (see `BloomObject::new_reserved_ribbon`); since the banded solve
+/// needs every item up front, the filter stays in `Pending` (a plain item list) and behaves like a linear
+/// scan until it fills to `capacity`, at which point it freezes into its compact `Sealed` form - mirroring
+/// the request this was built for: only the sub-structure that has become full and immutable is worth
+/// converting.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RibbonFilter {
+    state: RibbonState,
+    seed: u32,
+    capacity: i64,
+    fp_rate: f64,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+enum RibbonState {
+    Pending(Vec<Vec<u8>>),
+    Sealed { num_rows: u64, solution: Vec<u8> },
+}
+
+impl RibbonFilter {
+    pub fn new_reserved(capacity: i64, fp_rate: f64, seed: u32) -> Result<RibbonFilter, BloomError> {
+        if !(fp_rate > 0.0 && fp_rate < 1.0) {
+            return Err(BloomError::ErrorRateRange);
+        }
+        if capacity <= 0 {
+            return Err(BloomError::BadCapacity);
+        }
+        Ok(RibbonFilter {
+            state: RibbonState::Pending(Vec::new()),
+            seed,
+            capacity,
+            fp_rate,
+        })
+    }
+
+    /// Derives a key's equation: a start row `s`, a `COEFF_BITS`-wide coefficient `c`, and an 8-bit result
+    /// value, all from one 128-bit murmur3 hash. The 8-bit result width sets a sealed filter's false
+    /// positive rate at about 1/256, independent of `fp_rate`.
+    fn hash_parts(item: &[u8], seed: u32, num_rows: u64) -> (u64, u64, u8) {
+        let hash = murmur3::hash128_with_seed(item, seed);
+        let start = (hash as u64) % (num_rows - COEFF_BITS as u64 + 1);
+        // Force the top coefficient bit on so every equation's coefficient is non-zero and the banding
+        // loop below always terminates in a bounded number of steps.
+        let coeff = ((hash >> 64) as u64) | (1 << (COEFF_BITS - 1));
+        let result = (hash >> 96) as u8;
+        (start, coeff, result)
+    }
+
+    /// Attempts to solve the banded system for `items` over `num_rows` rows. Returns `None` if banding
+    /// fails - either a genuine GF(2) contradiction, or (see the comment at the `aligned >> offset` check below)
+    /// this implementation's simplifying assumption that a combined equation never needs to reach outside
+    /// its own `COEFF_BITS`-wide window. Either way the caller grows `num_rows` and retries.
+    fn try_band(items: &[Vec<u8>], seed: u32, num_rows: u64) -> Option<Vec<u8>> {
+        let mut equations: Vec<(u64, u64, u8)> = items
+            .iter()
+            .map(|item| Self::hash_parts(item, seed, num_rows))
+            .collect();
+        equations.sort_by_key(|&(start, _, _)| start);
+
+        let mut pivot_of: Vec<Option<(u64, u8)>> = vec![None; num_rows as usize];
+        for (start, mut coeff, mut result) in equations {
+            loop {
+                if coeff == 0 {
+                    if result != 0 {
+                        return None;
+                    }
+                    break;
+                }
+                let offset = coeff.trailing_zeros();
+                let row = start + offset as u64;
+                if row >= num_rows {
+                    return None;
+                }
+                match pivot_of[row as usize] {
+                    None => {
+                        pivot_of[row as usize] = Some((coeff >> offset, result));
+                        break;
+                    }
+                    Some((pivot_coeff, pivot_result)) => {
+                        let aligned = pivot_coeff << offset;
+                        // If shifting left by `offset` pushed out any set bits, `pivot_coeff` reached past
+                        // this equation's window and can't be combined losslessly - treat as a banding
+                        // failure rather than silently dropping those rows.
+                        if aligned >> offset != pivot_coeff {
+                            return None;
+                        }
+                        coeff ^= aligned;
+                        result ^= pivot_result;
+                    }
+                }
+            }
+        }
+
+        let mut solution = vec![0u8; num_rows as usize];
+        for row in (0..num_rows as usize).rev() {
+            let Some((coeff, result)) = pivot_of[row] else {
+                continue;
+            };
+            let mut acc = result;
+            let mut other_rows = coeff & !1;
+            while other_rows != 0 {
+                let j = other_rows.trailing_zeros() as usize;
+                acc ^= solution[row + j];
+                other_rows &= other_rows - 1;
+            }
+            solution[row] = acc;
+        }
+        Some(solution)
+    }
+
+    /// Solves the banded system for `items`, reseeding on every failed attempt and growing the row count
+    /// by ~5% (the standard Ribbon filter overhead factor) after every fourth one, until one succeeds.
+    /// Returns the row count, the solution, and the seed the successful attempt hashed with.
+    fn seal(items: &[Vec<u8>], seed: u32) -> (u64, Vec<u8>, u32) {
+        let min_rows = (items.len() as u64 + COEFF_BITS as u64).max(COEFF_BITS as u64);
+        let mut num_rows = min_rows;
+        let mut attempt_seed = seed;
+        loop {
+            if let Some(solution) = Self::try_band(items, attempt_seed, num_rows) {
+                return (num_rows, solution, attempt_seed);
+            }
+            attempt_seed = attempt_seed.wrapping_add(1);
+            // Only grow the row count once every salt at this size has been tried a few times, so a
+            // handful of retries can succeed without inflating the filter.
+            if attempt_seed.wrapping_sub(seed) % 4 == 0 {
+                num_rows += num_rows / 20 + 1;
+            }
+        }
+    }
+
+    /// Inserts `item`. While the filter hasn't reached `capacity` yet it's held in a plain, exact item
+    /// list; the moment it fills, every item collected so far (including this one) is solved into the
+    /// compact static form in one shot.
+    pub fn set(&mut self, item: &[u8]) {
+        let RibbonState::Pending(items) = &mut self.state else {
+            return;
+        };
+        items.push(item.to_vec());
+        if items.len() as i64 >= self.capacity {
+            let (num_rows, solution, seed) = Self::seal(items, self.seed);
+            // `check` must hash with the seed the solution was built under, which differs from the
+            // original one whenever `seal` had to retry.
+            self.seed = seed;
+            self.state = RibbonState::Sealed { num_rows, solution };
+        }
+    }
+
+    pub fn check(&self, item: &[u8]) -> bool {
+        match &self.state {
+            RibbonState::Pending(items) => items.iter().any(|i| i.as_slice() == item),
+            RibbonState::Sealed { num_rows, solution } => {
+                let (start, coeff, result) = Self::hash_parts(item, self.seed, *num_rows);
+                let mut acc = 0u8;
+                let mut bits = coeff;
+                while bits != 0 {
+                    let j = bits.trailing_zeros() as u64;
+                    acc ^= solution[(start + j) as usize];
+                    bits &= bits - 1;
+                }
+                acc == result
+            }
+        }
+    }
+
+    pub fn is_sealed(&self) -> bool {
+        matches!(self.state, RibbonState::Sealed { .. })
+    }
+
+    pub fn capacity(&self) -> i64 {
+        self.capacity
+    }
+
+    pub fn fp_rate(&self) -> f64 {
+        self.fp_rate
+    }
+
+    pub fn num_items(&self) -> i64 {
+        match &self.state {
+            RibbonState::Pending(items) => items.len() as i64,
+            RibbonState::Sealed { .. } => self.capacity,
+        }
+    }
+
+    pub fn seed(&self) -> u32 {
+        self.seed
+    }
+
+    pub fn number_of_bytes(&self) -> usize {
+        let state_bytes = match &self.state {
+            RibbonState::Pending(items) => items.iter().map(|i| i.len()).sum::<usize>(),
+            RibbonState::Sealed { solution, .. } => solution.len(),
+        };
+        std::mem::size_of::<RibbonFilter>() + state_bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ribbon_insert_and_check_pending() {
+        let mut filter = RibbonFilter::new_reserved(1000, 0.01, 0).unwrap();
+        for i in 0..500 {
+            filter.set(format!("item-{i}").as_bytes());
+        }
+        assert!(!filter.is_sealed());
+        for i in 0..500 {
+            assert!(filter.check(format!("item-{i}").as_bytes()));
+        }
+    }
+
+    #[test]
+    fn test_ribbon_seals_once_full_and_checks_hold() {
+        let mut filter = RibbonFilter::new_reserved(500, 0.01, 0).unwrap();
+        let present: Vec<Vec<u8>> = (0..500).map(|i| format!("item-{i}").into_bytes()).collect();
+        for item in &present {
+            filter.set(item);
+        }
+        assert!(filter.is_sealed());
+        for item in &present {
+            assert!(filter.check(item));
+        }
+        let false_positives = (0..500)
+            .map(|i| format!("absent-{i}").into_bytes())
+            .filter(|item| filter.check(item))
+            .count();
+        assert!(
+            false_positives < 50,
+            "false positive rate too high: {false_positives}/500"
+        );
+    }
+
+    #[test]
+    fn test_ribbon_rejects_bad_sizing() {
+        assert_eq!(
+            RibbonFilter::new_reserved(0, 0.01, 0).err(),
+            Some(BloomError::BadCapacity)
+        );
+        assert_eq!(
+            RibbonFilter::new_reserved(100, 1.5, 0).err(),
+            Some(BloomError::ErrorRateRange)
+        );
+    }
+}
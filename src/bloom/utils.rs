@@ -1,4 +1,11 @@
-use super::data_type::BLOOM_OBJECT_VERSION;
+use super::compression;
+use super::data_type::{
+    BLOOM_OBJECT_COMPRESSED_VERSION, BLOOM_OBJECT_SBBF_VERSION, BLOOM_OBJECT_VERSION,
+};
+use super::migration;
+use super::murmur128::Murmur128Filter;
+use super::ribbon::RibbonFilter;
+use super::sbbf::SplitBlockFilter;
 use crate::{
     configs::{
         self, BLOOM_EXPANSION_MAX, BLOOM_FP_RATE_MAX, BLOOM_FP_RATE_MIN,
@@ -6,6 +13,7 @@ use crate::{
     },
     metrics,
 };
+use bincode::Options;
 use bloomfilter::Bloom;
 use bloomfilter::{deserialize, serialize};
 use serde::{Deserialize, Deserializer, Serialize};
@@ -14,6 +22,8 @@ use std::sync::atomic::Ordering;
 /// KeySpace Notification Events
 pub const ADD_EVENT: &str = "bloom.add";
 pub const RESERVE_EVENT: &str = "bloom.reserve";
+pub const MERGE_EVENT: &str = "bloom.merge";
+pub const DEL_EVENT: &str = "bloom.del";
 
 /// Client Errors
 pub const ERROR: &str = "ERROR";
@@ -43,9 +53,39 @@ pub const DECODE_UNSUPPORTED_VERSION: &str =
     "ERR bloom object decoding failed. Unsupported version";
 pub const NON_SCALING_AND_VALIDATE_SCALE_TO_IS_INVALID: &str =
     "ERR cannot use NONSCALING and VALIDATESCALETO options together";
+pub const MERGE_FILTERS_INCOMPATIBLE: &str =
+    "ERR source filters are not compatible for a merge";
+pub const INVALID_CHUNK: &str = "ERR invalid chunk";
+pub const NOT_A_COUNTING_FILTER: &str = "ERR not a counting filter";
+pub const BAD_COUNTING_BITS: &str = "ERR bad counting bits";
+pub const COUNTING_MODE_MISMATCH: &str =
+    "ERR COUNTING option does not match the filter's existing counting mode";
+pub const COUNTING_FILTER_SATURATED: &str =
+    "ERR counting filter has a saturated counter; delete is unreliable";
+pub const BAD_BITMAP_COMPRESSION: &str =
+    "ERR bloom-bitmap-compression must be one of: none, snappy, lz4";
+pub const BAD_SBBF_DATA: &str =
+    "ERR provided split block bloom filter data is not a valid multiple of the 32 byte block size";
+pub const SBBF_READ_ONLY: &str =
+    "ERR split block bloom filters are populated at reserve/load time and cannot be added to afterwards";
+pub const BAD_JOBS_COUNT: &str = "ERR jobs count must be a positive integer";
+pub const BAD_HASH_ALGORITHM: &str = "ERR hash algorithm must be one of: default, murmur128";
+pub const BAD_FILTER_ALGORITHM: &str = "ERR bloom-filter-algorithm must be one of: bloom, ribbon";
+pub const RIBBON_REQUIRES_NONSCALING: &str =
+    "ERR bloom-filter-algorithm ribbon requires NONSCALING and is incompatible with COUNTING";
+pub const EXCEEDS_GLOBAL_MEMORY_BUDGET: &str =
+    "ERR operation exceeds the bloom-total-memory-limit global memory budget";
+pub const NOT_SBBF: &str =
+    "ERR BF.EXPORT/BF.IMPORT only supports filters created with BF.RESERVE ... SBBF";
+pub const ALLOCATION_FAILED: &str = "ERR failed to allocate memory for bloom object";
 /// Logging Error messages
 pub const ENCODE_BLOOM_OBJECT_FAILED: &str = "Failed to encode bloom object.";
 
+/// Bitmap bytes per additional unit of `BloomObject::free_effort`, on top of the flat one unit per
+/// sub-filter. Keeps a handful of huge scaled-out filters from reporting the same effort as a key with a
+/// few small ones.
+const FREE_EFFORT_BYTES_PER_UNIT: usize = 1 << 20;
+
 #[derive(Debug, PartialEq)]
 pub enum BloomError {
     NonScalingFilterFull,
@@ -60,6 +100,17 @@ pub enum BloomError {
     BadCapacity,
     ValidateScaleToExceedsMaxSize,
     ValidateScaleToFalsePositiveInvalid,
+    MergeFiltersIncompatible,
+    NotACountingFilter,
+    BadCountingBits,
+    CountingFilterSaturated,
+    BadBitmapCompression,
+    BadSbbfData,
+    SbbfReadOnly,
+    BadJobsCount,
+    ExceedsGlobalMemoryBudget,
+    NotSbbf,
+    AllocationFailed,
 }
 
 impl BloomError {
@@ -79,6 +130,17 @@ impl BloomError {
             BloomError::ValidateScaleToFalsePositiveInvalid => {
                 VALIDATE_SCALE_TO_FALSE_POSITIVE_INVALID
             }
+            BloomError::MergeFiltersIncompatible => MERGE_FILTERS_INCOMPATIBLE,
+            BloomError::NotACountingFilter => NOT_A_COUNTING_FILTER,
+            BloomError::BadCountingBits => BAD_COUNTING_BITS,
+            BloomError::CountingFilterSaturated => COUNTING_FILTER_SATURATED,
+            BloomError::BadBitmapCompression => BAD_BITMAP_COMPRESSION,
+            BloomError::BadSbbfData => BAD_SBBF_DATA,
+            BloomError::SbbfReadOnly => SBBF_READ_ONLY,
+            BloomError::BadJobsCount => BAD_JOBS_COUNT,
+            BloomError::ExceedsGlobalMemoryBudget => EXCEEDS_GLOBAL_MEMORY_BUDGET,
+            BloomError::NotSbbf => NOT_SBBF,
+            BloomError::AllocationFailed => ALLOCATION_FAILED,
         }
     }
 }
@@ -94,6 +156,39 @@ pub struct BloomObject {
     tightening_ratio: f64,
     is_seed_random: bool,
     filters: Vec<Box<BloomFilter>>,
+    /// Counter width (in bits) every sub-filter uses when this object was created with `COUNTING`, or
+    /// `None` for a plain bloom object. Stored here (rather than inferred per sub-filter) so scale-out
+    /// creates new sub-filters in the same counting mode. See `BloomFilter`'s `counting` field.
+    counting_bits: Option<u8>,
+    /// Present instead of `filters` when this object was created with `BF.RESERVE ... SBBF`: a Parquet-
+    /// compatible Split Block Bloom Filter rather than the scaling set of `BloomFilter`s every other
+    /// object uses. An SBBF doesn't scale and doesn't support `COUNTING`, so when this is `Some`, `filters`
+    /// is always empty. See `new_reserved_sbbf`.
+    sbbf: Option<Box<SplitBlockFilter>>,
+    /// Present instead of `filters` when this object was created with `BF.RESERVE ... HASH MURMUR128`: a
+    /// single fixed-size filter hashed with 128-bit MurmurHash3 instead of the sip-hash based default. Like
+    /// an SBBF-backed object, it doesn't scale and doesn't support `COUNTING`, so when this is `Some`,
+    /// `filters` is always empty. See `new_reserved_murmur128`.
+    murmur128: Option<Box<Murmur128Filter>>,
+    /// Present instead of `filters` when this object was created with `bloom-filter-algorithm ribbon`: a
+    /// single fixed-size filter that stays an exact item list until it fills to capacity, then seals into
+    /// a banded, statically-solved Ribbon encoding. Like an SBBF/murmur128-backed object, it doesn't scale
+    /// and doesn't support `COUNTING`, so when this is `Some`, `filters` is always empty. See
+    /// `new_reserved_ribbon`.
+    ribbon: Option<Box<RibbonFilter>>,
+}
+
+/// The header chunk emitted as chunk `0` of a `BF.SCANDUMP` stream. Captures everything needed to
+/// reconstruct a `BloomObject` other than the sub-filters themselves, which are streamed as their own
+/// chunks. See `BloomObject::encode_scandump_chunk` / `decode_scandump_header`.
+#[derive(Serialize, Deserialize)]
+struct ScandumpHeader {
+    expansion: u32,
+    fp_rate: f64,
+    tightening_ratio: f64,
+    is_seed_random: bool,
+    num_filters: usize,
+    counting_bits: Option<u8>,
 }
 
 impl BloomObject {
@@ -105,53 +200,272 @@ impl BloomObject {
         expansion: u32,
         seed: (Option<[u8; 32]>, bool),
         validate_size_limit: bool,
+    ) -> Result<BloomObject, BloomError> {
+        Self::new_reserved_with_counting(
+            fp_rate,
+            tightening_ratio,
+            capacity,
+            expansion,
+            seed,
+            validate_size_limit,
+            None,
+        )
+    }
+
+    /// Create a new BloomObject object, optionally in counting mode. When `counting_bits` is
+    /// `Some(width)`, every sub-filter (including ones created later by scale-out) stores a `width`-bit
+    /// saturating counter per hashed slot instead of a single bit, enabling `BF.DEL`. See the `COUNTING`
+    /// option on `BF.RESERVE`/`BF.INSERT`.
+    pub fn new_reserved_with_counting(
+        fp_rate: f64,
+        tightening_ratio: f64,
+        capacity: i64,
+        expansion: u32,
+        seed: (Option<[u8; 32]>, bool),
+        validate_size_limit: bool,
+        counting_bits: Option<u8>,
     ) -> Result<BloomObject, BloomError> {
         // Reject the request, if the operation will result in creation of a bloom object
         // of size greater than what is allowed.
-        if validate_size_limit && !BloomObject::validate_size_before_create(capacity, fp_rate) {
-            return Err(BloomError::ExceedsMaxBloomSize);
+        if validate_size_limit {
+            if !BloomObject::validate_size_before_create(capacity, fp_rate, counting_bits) {
+                return Err(BloomError::ExceedsMaxBloomSize);
+            }
+            let bytes = BloomObject::bytes_for_create(capacity, fp_rate, counting_bits);
+            if !BloomObject::validate_global_memory_budget(bytes) {
+                return Err(BloomError::ExceedsGlobalMemoryBudget);
+            }
         }
         // Create the bloom filter and add to the main Bloom object.
         let is_seed_random;
         let bloom = match seed {
             (None, _) => {
                 is_seed_random = true;
-                Box::new(BloomFilter::with_random_seed(fp_rate, capacity))
+                Box::new(BloomFilter::with_random_seed_counting(
+                    fp_rate,
+                    capacity,
+                    counting_bits,
+                )?)
             }
             (Some(seed), is_random) => {
                 is_seed_random = is_random;
-                Box::new(BloomFilter::with_fixed_seed(fp_rate, capacity, &seed))
+                Box::new(BloomFilter::with_fixed_seed_counting(
+                    fp_rate,
+                    capacity,
+                    &seed,
+                    counting_bits,
+                )?)
             }
         };
-        let filters = vec![bloom];
+        let mut filters: Vec<Box<BloomFilter>> = Vec::new();
+        filters
+            .try_reserve_exact(1)
+            .map_err(|_| BloomError::AllocationFailed)?;
+        filters.push(bloom);
         let bloom = BloomObject {
             expansion,
             fp_rate,
             tightening_ratio,
             filters,
             is_seed_random,
+            counting_bits,
+            sbbf: None,
+            murmur128: None,
+            ribbon: None,
+        };
+        bloom.bloom_object_incr_metrics_on_new_create();
+        Ok(bloom)
+    }
+
+    /// Create a new BloomObject backed by a single fixed-size filter hashed with 128-bit MurmurHash3
+    /// instead of the sip-hash based default, sized for `capacity` items at false positive rate `fp_rate`.
+    /// See the `HASH MURMUR128` option on `BF.RESERVE`. Like an SBBF-backed object, the result is
+    /// non-scaling and does not support `COUNTING`.
+    pub fn new_reserved_murmur128(
+        capacity: i64,
+        fp_rate: f64,
+        seed: u32,
+        validate_size_limit: bool,
+    ) -> Result<BloomObject, BloomError> {
+        let murmur = Murmur128Filter::new_reserved(capacity, fp_rate, seed)?;
+        if validate_size_limit {
+            let bytes = std::mem::size_of::<BloomObject>() + murmur.number_of_bytes();
+            if !BloomObject::validate_size(bytes) {
+                return Err(BloomError::ExceedsMaxBloomSize);
+            }
+            if !BloomObject::validate_global_memory_budget(bytes) {
+                return Err(BloomError::ExceedsGlobalMemoryBudget);
+            }
+        }
+        let bloom = BloomObject {
+            expansion: 0,
+            fp_rate,
+            tightening_ratio: 1.0,
+            is_seed_random: true,
+            filters: Vec::new(),
+            counting_bits: None,
+            sbbf: None,
+            murmur128: Some(Box::new(murmur)),
+            ribbon: None,
+        };
+        bloom.bloom_object_incr_metrics_on_new_create();
+        Ok(bloom)
+    }
+
+    /// Create a new BloomObject backed by a single fixed-size Ribbon filter (see `bloom::ribbon`), sized
+    /// for `capacity` items. `fp_rate` is validated and recorded, but once sealed the filter's false
+    /// positive rate is about 1/256 (its 8-bit result width) whatever `fp_rate` is. Selected via `bloom-filter-algorithm ribbon`
+    /// on a `NONSCALING` reserve. Like an SBBF/murmur128-backed object, the result does not support
+    /// `COUNTING` or scaling; unlike them it starts as an exact item list and only reaches its compact
+    /// encoding once `capacity` items have been added.
+    pub fn new_reserved_ribbon(
+        capacity: i64,
+        fp_rate: f64,
+        seed: u32,
+        validate_size_limit: bool,
+    ) -> Result<BloomObject, BloomError> {
+        let ribbon = RibbonFilter::new_reserved(capacity, fp_rate, seed)?;
+        if validate_size_limit {
+            let bytes = std::mem::size_of::<BloomObject>() + ribbon.number_of_bytes();
+            if !BloomObject::validate_size(bytes) {
+                return Err(BloomError::ExceedsMaxBloomSize);
+            }
+            if !BloomObject::validate_global_memory_budget(bytes) {
+                return Err(BloomError::ExceedsGlobalMemoryBudget);
+            }
+        }
+        let bloom = BloomObject {
+            expansion: 0,
+            fp_rate,
+            tightening_ratio: 1.0,
+            is_seed_random: true,
+            filters: Vec::new(),
+            counting_bits: None,
+            sbbf: None,
+            murmur128: None,
+            ribbon: Some(Box::new(ribbon)),
+        };
+        bloom.bloom_object_incr_metrics_on_new_create();
+        Ok(bloom)
+    }
+
+    /// Create a new BloomObject backed by a Parquet-compatible Split Block Bloom Filter instead of the
+    /// usual scaling set of sub-filters, sized for `ndv` distinct values at false positive rate `fp_rate`.
+    /// See the `SBBF` option on `BF.RESERVE`. Unlike a standard bloom object, the result is non-scaling and
+    /// does not support `COUNTING` - `add_item` returns `BloomError::SbbfReadOnly` once created; it is
+    /// populated via `BF.LOAD` or `BF.IMPORT` instead.
+    pub fn new_reserved_sbbf(
+        ndv: i64,
+        fp_rate: f64,
+        validate_size_limit: bool,
+    ) -> Result<BloomObject, BloomError> {
+        let sbbf = SplitBlockFilter::new_with_ndv_fpp(ndv, fp_rate)?;
+        if validate_size_limit {
+            let bytes = std::mem::size_of::<BloomObject>() + sbbf.number_of_bytes();
+            if !BloomObject::validate_size(bytes) {
+                return Err(BloomError::ExceedsMaxBloomSize);
+            }
+            if !BloomObject::validate_global_memory_budget(bytes) {
+                return Err(BloomError::ExceedsGlobalMemoryBudget);
+            }
+        }
+        let bloom = BloomObject {
+            expansion: 0,
+            fp_rate,
+            tightening_ratio: 1.0,
+            is_seed_random: true,
+            filters: Vec::new(),
+            counting_bits: None,
+            sbbf: Some(Box::new(sbbf)),
+            murmur128: None,
+            ribbon: None,
+        };
+        bloom.bloom_object_incr_metrics_on_new_create();
+        Ok(bloom)
+    }
+
+    /// Create a new BloomObject backed by a Split Block Bloom Filter imported from a `BF.EXPORT`-produced
+    /// blob (or one built from scratch in this module's own export framing - see `SplitBlockFilter::export`
+    /// for why that's not a generic Parquet `BloomFilterHeader` blob). See `SplitBlockFilter::import` and
+    /// the `BF.IMPORT` command. `ndv` is the number of distinct values the caller knows the imported filter
+    /// was built for - the Parquet wire format itself doesn't carry it, so it isn't recoverable from `bytes`
+    /// alone.
+    pub fn new_imported_sbbf(
+        bytes: &[u8],
+        ndv: i64,
+        fp_rate: f64,
+        validate_size_limit: bool,
+    ) -> Result<BloomObject, BloomError> {
+        if !(fp_rate > BLOOM_FP_RATE_MIN && fp_rate < BLOOM_FP_RATE_MAX) {
+            return Err(BloomError::ErrorRateRange);
+        }
+        let sbbf = SplitBlockFilter::import(bytes, ndv)?;
+        if validate_size_limit {
+            let bytes = std::mem::size_of::<BloomObject>() + sbbf.number_of_bytes();
+            if !BloomObject::validate_size(bytes) {
+                return Err(BloomError::ExceedsMaxBloomSize);
+            }
+            if !BloomObject::validate_global_memory_budget(bytes) {
+                return Err(BloomError::ExceedsGlobalMemoryBudget);
+            }
+        }
+        let bloom = BloomObject {
+            expansion: 0,
+            fp_rate,
+            tightening_ratio: 1.0,
+            is_seed_random: true,
+            filters: Vec::new(),
+            counting_bits: None,
+            sbbf: Some(Box::new(sbbf)),
+            murmur128: None,
+            ribbon: None,
         };
         bloom.bloom_object_incr_metrics_on_new_create();
         Ok(bloom)
     }
 
-    /// Create a BloomObject from existing data (RDB Load / Restore).
+    /// Create a BloomObject from existing data (`BF.LOADCHUNK` reassembly of a `BF.SCANDUMP` stream).
+    /// Each sub-filter chunk was deserialized directly via `bincode` rather than through one of
+    /// `BloomFilter`'s constructors, so it hasn't yet registered its own memory metrics; do that here
+    /// before validating the reassembled object's size, mirroring `decode_object`'s version-1 branch.
     pub fn from_existing(
         expansion: u32,
         fp_rate: f64,
         tightening_ratio: f64,
         is_seed_random: bool,
         filters: Vec<Box<BloomFilter>>,
-    ) -> BloomObject {
+        counting_bits: Option<u8>,
+        validate_size_limit: bool,
+    ) -> Result<BloomObject, BloomError> {
+        for filter in &filters {
+            metrics::BLOOM_NUM_ITEMS_ACROSS_OBJECTS.fetch_add(
+                filter.num_items as u64,
+                std::sync::atomic::Ordering::Relaxed,
+            );
+            filter.bloom_filter_incr_metrics_on_new_create();
+        }
         let bloom = BloomObject {
             expansion,
             fp_rate,
             tightening_ratio,
             is_seed_random,
             filters,
+            counting_bits,
+            sbbf: None,
+            murmur128: None,
+            ribbon: None,
         };
         bloom.bloom_object_incr_metrics_on_new_create();
-        bloom
+        if validate_size_limit {
+            let bytes = bloom.memory_usage();
+            if !BloomObject::validate_size(bytes) {
+                return Err(BloomError::ExceedsMaxBloomSize);
+            }
+            if !BloomObject::validate_global_memory_budget(0) {
+                return Err(BloomError::ExceedsGlobalMemoryBudget);
+            }
+        }
+        Ok(bloom)
     }
 
     /// Create a new BloomObject from an existing one (COPY).
@@ -165,8 +479,12 @@ impl BloomObject {
             expansion: from_bf.expansion,
             fp_rate: from_bf.fp_rate,
             tightening_ratio: from_bf.tightening_ratio,
+            sbbf: from_bf.sbbf.clone(),
+            murmur128: from_bf.murmur128.clone(),
+            ribbon: from_bf.ribbon.clone(),
             is_seed_random: from_bf.is_seed_random,
             filters,
+            counting_bits: from_bf.counting_bits,
         };
         new_copy.bloom_object_incr_metrics_on_new_create();
         new_copy
@@ -175,17 +493,142 @@ impl BloomObject {
     /// Return the total memory usage of the BloomObject and every allocation it contains.
     pub fn memory_usage(&self) -> usize {
         let mut mem: usize = self.bloom_object_memory_usage();
+        if let Some(sbbf) = &self.sbbf {
+            return mem + sbbf.number_of_bytes();
+        }
+        if let Some(murmur128) = &self.murmur128 {
+            return mem + murmur128.number_of_bytes();
+        }
+        if let Some(ribbon) = &self.ribbon {
+            return mem + ribbon.number_of_bytes();
+        }
         for filter in &self.filters {
             mem += filter.number_of_bytes();
         }
         mem
     }
 
+    /// Returns whether this object is backed by a Split Block Bloom Filter (`BF.RESERVE ... SBBF`) rather
+    /// than the usual scaling set of sub-filters.
+    pub fn is_sbbf(&self) -> bool {
+        self.sbbf.is_some()
+    }
+
+    /// Serializes this object's bitset for `BF.EXPORT`, or `BloomError::NotSbbf` if this object isn't
+    /// SBBF-backed (see `new_reserved_sbbf`). Only the bitset body is genuinely Parquet-wire-compatible -
+    /// see `SplitBlockFilter::export` for the caveat on the blob's own header.
+    pub fn export_sbbf(&self) -> Result<Vec<u8>, BloomError> {
+        self.sbbf
+            .as_ref()
+            .map(|sbbf| sbbf.export())
+            .ok_or(BloomError::NotSbbf)
+    }
+
+    /// Returns whether this object is backed by a single 128-bit MurmurHash3 filter (`BF.RESERVE ... HASH
+    /// MURMUR128`) rather than the usual scaling set of sip-hash based sub-filters.
+    pub fn is_murmur128(&self) -> bool {
+        self.murmur128.is_some()
+    }
+
+    /// Returns whether this object is backed by a single Ribbon filter (`bloom-filter-algorithm ribbon`)
+    /// rather than the usual scaling set of sip-hash based sub-filters.
+    pub fn is_ribbon(&self) -> bool {
+        self.ribbon.is_some()
+    }
+
     /// Calculates the memory usage of the BloomObject structure (not its nested allocations).
     fn bloom_object_memory_usage(&self) -> usize {
         BloomObject::compute_size(self.filters.capacity())
     }
 
+    /// Returns the number of bytes used by the `BloomObject` structure itself, excluding its sub-filters'
+    /// bitmaps. Exposed for `BF.INFO ... MEMORY`'s byte breakdown.
+    pub fn overhead_bytes(&self) -> usize {
+        self.bloom_object_memory_usage()
+    }
+
+    /// Returns `(bytes, bits_set, bits_total)` for every sub-filter, in scale-out order. `bits_set` and
+    /// `bits_total` let a caller derive each sub-filter's fill ratio without requiring floating point in
+    /// the wire protocol; a saturated sub-filter (fill ratio near 1) predicts an imminent scale-up.
+    pub fn filter_memory_breakdown(&self) -> Vec<(usize, u64, u64)> {
+        self.filters
+            .iter()
+            .map(|filter| {
+                (
+                    filter.number_of_bytes(),
+                    filter.bits_set(),
+                    filter.bits_total(),
+                )
+            })
+            .collect()
+    }
+
+    /// Returns each sub-filter's `(capacity, items, bytes, fill_ratio)`, in scale-out order. Backs
+    /// `BF.INFO ... FILTERSDETAIL`. Empty for an SBBF-backed object, which has no sub-filters.
+    pub fn filter_detail_breakdown(&self) -> Vec<(i64, i64, usize, f64)> {
+        self.filters
+            .iter()
+            .map(|filter| {
+                (
+                    filter.capacity(),
+                    filter.num_items(),
+                    filter.number_of_bytes(),
+                    filter.fill_ratio(),
+                )
+            })
+            .collect()
+    }
+
+    /// Returns the fraction of bits currently set across every sub-filter's backing bitmap, combined.
+    /// `BF.INFO ... FILLRATIO`. SBBF-backed objects are not covered and report `0.0`.
+    pub fn fill_ratio(&self) -> f64 {
+        if self.sbbf.is_some() {
+            return 0.0;
+        }
+        if let Some(murmur128) = &self.murmur128 {
+            return murmur128.fill_ratio();
+        }
+        if self.ribbon.is_some() {
+            // A Ribbon filter's solved matrix has no meaningful "bits set" concept once sealed, and while
+            // still pending it's an exact item list with no bitmap at all.
+            return 0.0;
+        }
+        let (bits_set, bits_total) = self
+            .filters
+            .iter()
+            .fold((0u64, 0u64), |(set_acc, total_acc), filter| {
+                (set_acc + filter.bits_set(), total_acc + filter.bits_total())
+            });
+        if bits_total == 0 {
+            return 0.0;
+        }
+        bits_set as f64 / bits_total as f64
+    }
+
+    /// Returns the realized false-positive probability estimated from every sub-filter's current bit-fill,
+    /// as opposed to `fp_rate()` which is the configured target. `BF.INFO ... CURRENTERROR`. A lookup
+    /// against a scaling object checks every sub-filter, so the combined probability of a false positive is
+    /// `1 - product(1 - p_i)` over all of them, reducing to a single filter's own estimate in the common
+    /// (unscaled) case. SBBF-backed objects are not covered and report `0.0`.
+    pub fn current_error_rate(&self) -> f64 {
+        if self.sbbf.is_some() {
+            return 0.0;
+        }
+        if let Some(murmur128) = &self.murmur128 {
+            return murmur128.current_error_rate();
+        }
+        if let Some(ribbon) = &self.ribbon {
+            // Exact (zero false positives) while pending; once sealed a non-member matches its 8-bit
+            // result value by chance about 1 time in 256, whatever `fp_rate` was configured.
+            return if ribbon.is_sealed() { 1.0 / 256.0 } else { 0.0 };
+        }
+        let combined_miss_probability = self
+            .filters
+            .iter()
+            .fold(1.0, |acc, filter| acc * (1.0 - filter.current_error_rate()));
+        1.0 - combined_miss_probability
+    }
+
     /// Calculates the memory usage of the BloomObject structure (not its nested allocations). Used when `self` is unavailable.
     pub fn compute_size(filters_vec_capacity: usize) -> usize {
         std::mem::size_of::<BloomObject>()
@@ -197,18 +640,23 @@ impl BloomObject {
     /// size will be within the allowed size limit.
     /// Returns whether the bloom object is of a valid size or not.
     fn validate_size_before_scaling(&self, capacity: i64, fp_rate: f64) -> bool {
-        let bytes = self.memory_usage() + BloomFilter::compute_size(capacity, fp_rate);
+        let bytes =
+            self.memory_usage() + BloomFilter::compute_size(capacity, fp_rate, self.counting_bits);
         BloomObject::validate_size(bytes)
     }
 
     /// Caculates the number of bytes that the bloom object will require to be allocated.
     /// This is used when creating a new bloom object to check if the size is within the allowed size limit.
-    /// Returns whether the bloom object is of a valid size or not.
-    fn validate_size_before_create(capacity: i64, fp_rate: f64) -> bool {
-        let bytes = std::mem::size_of::<BloomObject>()
+    fn bytes_for_create(capacity: i64, fp_rate: f64, counting_bits: Option<u8>) -> usize {
+        std::mem::size_of::<BloomObject>()
             + std::mem::size_of::<Box<BloomFilter>>()
-            + BloomFilter::compute_size(capacity, fp_rate);
-        BloomObject::validate_size(bytes)
+            + BloomFilter::compute_size(capacity, fp_rate, counting_bits)
+    }
+
+    /// This is used when creating a new bloom object to check if the size is within the allowed size limit.
+    /// Returns whether the bloom object is of a valid size or not.
+    fn validate_size_before_create(capacity: i64, fp_rate: f64, counting_bits: Option<u8>) -> bool {
+        BloomObject::validate_size(BloomObject::bytes_for_create(capacity, fp_rate, counting_bits))
     }
 
     /// Returns whether the bloom object is of a valid size or not.
@@ -219,19 +667,96 @@ impl BloomObject {
         true
     }
 
-    /// Returns the Bloom object's free_effort.
-    /// We return 1 if there are no filters (BF.RESERVE) or if there is 1 filter.
-    /// Else, we return the number of filters as the free_effort.
-    /// This is similar to how the core handles aggregated objects.
+    /// Returns whether reserving `marginal_bytes` of additional bloom memory would stay within the
+    /// module-wide `bloom-total-memory-limit` budget (`0` means unlimited, mirroring `maxmemory 0`).
+    /// Checked in addition to the per-object `BLOOM_MEMORY_LIMIT_PER_OBJECT` cap at every allocation path:
+    /// creation, scale-out, `from_existing` (`BF.LOADCHUNK` reassembly) and RDB/AOF restore. Sites where
+    /// `metrics::BLOOM_OBJECT_TOTAL_MEMORY_BYTES` has already been updated to include the bytes in
+    /// question (e.g. `decode_object`) should pass `0` so the running total isn't counted twice.
+    fn validate_global_memory_budget(marginal_bytes: usize) -> bool {
+        let limit = configs::BLOOM_TOTAL_MEMORY_LIMIT.load(Ordering::Relaxed);
+        if limit == 0 {
+            return true;
+        }
+        let projected = metrics::BLOOM_OBJECT_TOTAL_MEMORY_BYTES.load(Ordering::Relaxed) as i64
+            + marginal_bytes as i64;
+        projected <= limit
+    }
+
+    /// Returns the Bloom object's free_effort, which the core uses both to decide whether a key's
+    /// deletion is large enough to defer to a lazy-free background thread, and whether `bloom_defrag`
+    /// should be given a single synchronous pass or stepped through incrementally via its cursor.
+    ///
+    /// We return 1 if there are no filters (BF.RESERVE) or for a non-scaling alternative backend (SBBF,
+    /// MURMUR128, Ribbon). Otherwise we add the number of sub-filters to a term scaled by their total
+    /// bitmap bytes, so a handful of huge scaled-out filters reports enough effort to actually exercise
+    /// the multi-step path instead of looking the same as a key with a few small filters.
     pub fn free_effort(&self) -> usize {
-        self.filters.len()
+        if self.sbbf.is_some() || self.murmur128.is_some() || self.ribbon.is_some() {
+            return 1;
+        }
+        let total_bitmap_bytes: usize = self.filters.iter().map(|f| f.number_of_bytes()).sum();
+        self.filters.len() + total_bitmap_bytes / FREE_EFFORT_BYTES_PER_UNIT
     }
 
     /// Check if item exists already.
     pub fn item_exists(&self, item: &[u8]) -> bool {
+        if let Some(sbbf) = &self.sbbf {
+            return sbbf.check(item);
+        }
+        if let Some(murmur128) = &self.murmur128 {
+            return murmur128.check(item);
+        }
+        if let Some(ribbon) = &self.ribbon {
+            return ribbon.check(item);
+        }
         self.filters.iter().any(|filter| filter.check(item))
     }
 
+    /// Returns whether this object was created with `COUNTING` enabled.
+    pub fn is_counting(&self) -> bool {
+        self.counting_bits.is_some()
+    }
+
+    /// Returns the counter width (in bits) this object was created with, or `None` for a plain object.
+    pub fn counting_bits(&self) -> Option<u8> {
+        self.counting_bits
+    }
+
+    /// Returns whether any sub-filter's counters have ever saturated. A saturated counter can never be
+    /// decremented back to an exact value, so once this is `true`, a `BF.DEL` on this object may silently
+    /// leave a deleted item's bits set (or clear bits still shared by another item) and should be treated
+    /// as unreliable.
+    pub fn any_counter_saturated(&self) -> bool {
+        self.filters.iter().any(|filter| filter.counting_saturated())
+    }
+
+    /// Removes `item` from every sub-filter it currently tests positive in by decrementing the counters it
+    /// maps to, supporting `BF.DEL`. Returns `Ok(1)` if the item was present and its counters were
+    /// decremented, `Ok(0)` if it was not present, or `Err(BloomError::NotACountingFilter)` if this object
+    /// was not created with `COUNTING`.
+    pub fn delete_item(&mut self, item: &[u8]) -> Result<i64, BloomError> {
+        if self.counting_bits.is_none() {
+            return Err(BloomError::NotACountingFilter);
+        }
+        if self.any_counter_saturated() {
+            return Err(BloomError::CountingFilterSaturated);
+        }
+        if !self.item_exists(item) {
+            return Ok(0);
+        }
+        for filter in self.filters.iter_mut() {
+            if filter.check(item) {
+                filter.delete(item)?;
+                if filter.num_items > 0 {
+                    filter.num_items -= 1;
+                }
+            }
+        }
+        metrics::BLOOM_NUM_ITEMS_ACROSS_OBJECTS.fetch_sub(1, Ordering::Relaxed);
+        Ok(1)
+    }
+
     /// Return a count of number of items added to all sub filters in the BloomObject structure.
     pub fn cardinality(&self) -> i64 {
         let mut cardinality: i64 = 0;
@@ -241,8 +766,18 @@ impl BloomObject {
         cardinality
     }
 
-    /// Return a total capacity summed across all sub filters in the BloomObject structure.
+    /// Return a total capacity summed across all sub filters in the BloomObject structure, or the `ndv` it
+    /// was sized for if this object is SBBF-backed, or its configured capacity if murmur128/ribbon-backed.
     pub fn capacity(&self) -> i64 {
+        if let Some(sbbf) = &self.sbbf {
+            return sbbf.ndv();
+        }
+        if let Some(murmur128) = &self.murmur128 {
+            return murmur128.capacity();
+        }
+        if let Some(ribbon) = &self.ribbon {
+            return ribbon.capacity();
+        }
         let mut capacity: i64 = 0;
         // Check if item exists already.
         for filter in &self.filters {
@@ -252,15 +787,38 @@ impl BloomObject {
     }
 
     /// Return the seed used by the Bloom object. Every filter in the bloom object uses the same seed as the
-    /// first filter regardless if the seed is fixed or randomly generated.
+    /// first filter regardless if the seed is fixed or randomly generated. SBBF-backed objects don't use a
+    /// sip-hash seed at all (they hash with xxHash64), so this returns an all-zero placeholder for them.
+    /// A murmur128-backed object's own 32-bit murmur seed is reported separately by `murmur128_seed`.
+    /// Ribbon-backed objects hash with the same murmur3 primitive but aren't exposed through this getter
+    /// either, for the same reason.
     pub fn seed(&self) -> [u8; 32] {
+        if self.sbbf.is_some() || self.murmur128.is_some() || self.ribbon.is_some() {
+            return [0u8; 32];
+        }
         self.filters
             .first()
             .expect("Every BloomObject is expected to have at least one filter")
             .seed()
     }
-    /// Return the starting capacity used by the Bloom object. This capacity is held within the first filter
+
+    /// Return the 32-bit murmur3 seed this object was reserved with, or `None` if it isn't murmur128-backed.
+    pub fn murmur128_seed(&self) -> Option<u32> {
+        self.murmur128.as_ref().map(|m| m.seed())
+    }
+
+    /// Return the starting capacity used by the Bloom object. This capacity is held within the first filter,
+    /// or is the `ndv` an SBBF-backed object was sized for, or the configured capacity if murmur128/ribbon-backed.
     pub fn starting_capacity(&self) -> i64 {
+        if let Some(sbbf) = &self.sbbf {
+            return sbbf.ndv();
+        }
+        if let Some(murmur128) = &self.murmur128 {
+            return murmur128.capacity();
+        }
+        if let Some(ribbon) = &self.ribbon {
+            return ribbon.capacity();
+        }
         self.filters
             .first()
             .expect("Every BloomObject is expected to have at least one filter")
@@ -305,10 +863,34 @@ impl BloomObject {
     /// Add an item to the BloomObject structure.
     /// If scaling is enabled, this can result in a new sub filter creation.
     pub fn add_item(&mut self, item: &[u8], validate_size_limit: bool) -> Result<i64, BloomError> {
+        if self.sbbf.is_some() {
+            return Err(BloomError::SbbfReadOnly);
+        }
         // Check if item exists already.
         if self.item_exists(item) {
             return Ok(0);
         }
+        if let Some(murmur128) = &mut self.murmur128 {
+            // Murmur128-backed objects are a single fixed-size filter, same as an SBBF-backed object would
+            // be if it supported inserts - there is no sub-filter to scale out to.
+            if murmur128.num_items() >= murmur128.capacity() {
+                return Err(BloomError::NonScalingFilterFull);
+            }
+            murmur128.set(item);
+            murmur128.incr_num_items();
+            metrics::BLOOM_NUM_ITEMS_ACROSS_OBJECTS.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            return Ok(1);
+        }
+        if let Some(ribbon) = &mut self.ribbon {
+            // Like murmur128 above, a Ribbon-backed object is a single fixed-size filter with no sub-filter
+            // to scale out to; `set` itself seals it into the compact encoding once it reaches capacity.
+            if ribbon.num_items() >= ribbon.capacity() {
+                return Err(BloomError::NonScalingFilterFull);
+            }
+            ribbon.set(item);
+            metrics::BLOOM_NUM_ITEMS_ACROSS_OBJECTS.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            return Ok(1);
+        }
         let num_filters = self.filters.len() as i32;
         if let Some(filter) = self.filters.last_mut() {
             if filter.num_items < filter.capacity {
@@ -320,46 +902,14 @@ impl BloomObject {
                 return Ok(1);
             }
             // Non Scaling Filters that are filled to capacity cannot handle more inserts.
-            if self.expansion == 0 {
-                return Err(BloomError::NonScalingFilterFull);
-            }
-            if num_filters == configs::BLOOM_NUM_FILTERS_PER_OBJECT_LIMIT_MAX {
-                return Err(BloomError::MaxNumScalingFilters);
-            }
-            // Scale out by adding a new filter with capacity bounded within the u32 range. false positive rate is also
-            // bound within the range f64::MIN_POSITIVE <= x < 1.0.
-            let new_fp_rate =
-                Self::calculate_fp_rate(self.fp_rate, num_filters, self.tightening_ratio)?;
-            let new_capacity = match filter.capacity.checked_mul(self.expansion.into()) {
-                Some(new_capacity) => new_capacity,
-                None => {
-                    // With a 128MB memory limit for a bloom object overall, it is not possible to reach u32:max capacity.
-                    return Err(BloomError::BadCapacity);
-                }
-            };
-            // Reject the request, if the operation will result in creation of a filter of size greater than what is allowed.
-            if validate_size_limit && !self.validate_size_before_scaling(new_capacity, new_fp_rate)
-            {
-                return Err(BloomError::ExceedsMaxBloomSize);
-            }
-            let seed = self.seed();
-            let mut new_filter = Box::new(BloomFilter::with_fixed_seed(
-                new_fp_rate,
-                new_capacity,
-                &seed,
-            ));
-            let memory_usage_before: usize = self.bloom_object_memory_usage();
+            self.scale_out(validate_size_limit)?;
+            let new_filter = self
+                .filters
+                .last_mut()
+                .expect("scale_out always appends a filter");
             // Add item.
             new_filter.set(item);
             new_filter.num_items += 1;
-            self.filters.push(new_filter);
-            // If we went over capacity and scaled the vec out we need to update the memory usage by the new capacity
-            let memory_usage_after = self.bloom_object_memory_usage();
-
-            metrics::BLOOM_OBJECT_TOTAL_MEMORY_BYTES.fetch_add(
-                memory_usage_after - memory_usage_before,
-                std::sync::atomic::Ordering::Relaxed,
-            );
             metrics::BLOOM_NUM_ITEMS_ACROSS_OBJECTS
                 .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
             return Ok(1);
@@ -367,13 +917,474 @@ impl BloomObject {
         Ok(0)
     }
 
-    /// Serializes bloomFilter to a byte array.
+    /// Appends exactly one new sub-filter, sized `expansion`x the current last filter's capacity at the
+    /// next tightened fp rate - the same growth `add_item` reaches for once its active filter fills up.
+    /// Does not insert anything into the new filter; callers do that afterwards. Factored out so
+    /// `add_items` can call this in a loop to create however many sub-filters a whole batch needs in one
+    /// sweep, rather than re-deciding whether to scale out once per item the way repeated `add_item`
+    /// calls crossing the same boundary would.
+    fn scale_out(&mut self, validate_size_limit: bool) -> Result<(), BloomError> {
+        let num_filters = self.filters.len() as i32;
+        if self.expansion == 0 {
+            return Err(BloomError::NonScalingFilterFull);
+        }
+        if num_filters == configs::BLOOM_NUM_FILTERS_PER_OBJECT_LIMIT_MAX {
+            return Err(BloomError::MaxNumScalingFilters);
+        }
+        let last_capacity = self
+            .filters
+            .last()
+            .map(|f| f.capacity)
+            .expect("Every BloomObject is expected to have at least one filter");
+        // Scale out by adding a new filter with capacity bounded within the u32 range. false positive rate is also
+        // bound within the range f64::MIN_POSITIVE <= x < 1.0.
+        let new_fp_rate =
+            Self::calculate_fp_rate(self.fp_rate, num_filters, self.tightening_ratio)?;
+        let new_capacity = match last_capacity.checked_mul(self.expansion.into()) {
+            Some(new_capacity) => new_capacity,
+            None => {
+                // With a 128MB memory limit for a bloom object overall, it is not possible to reach u32:max capacity.
+                return Err(BloomError::BadCapacity);
+            }
+        };
+        // Reject the request, if the operation will result in creation of a filter of size greater than what is allowed.
+        if validate_size_limit {
+            if !self.validate_size_before_scaling(new_capacity, new_fp_rate) {
+                return Err(BloomError::ExceedsMaxBloomSize);
+            }
+            if !BloomObject::validate_global_memory_budget(BloomFilter::compute_size(
+                new_capacity,
+                new_fp_rate,
+                self.counting_bits,
+            )) {
+                return Err(BloomError::ExceedsGlobalMemoryBudget);
+            }
+        }
+        let seed = self.seed();
+        let prior_bias: i64 = self.filters.iter().map(|f| f.mem_rounding_bias()).sum();
+        // `bloom_object_memory_usage` bills this object for `self.filters.capacity()`, not `.len()`, so
+        // an amortized `try_reserve` here (which is free to double the allocation) would let a single
+        // scale-out inflate every subsequent size check - right up to pushing a legitimate next scale-out
+        // over `ExceedsMaxBloomSize`/`ExceedsGlobalMemoryBudget` for capacity the object was never actually
+        // charged for using. `try_reserve_exact` keeps capacity tracking `len` 1-for-1, mirroring the
+        // initial single-filter allocation in `new_reserved_with_counting`.
+        self.filters
+            .try_reserve_exact(1)
+            .map_err(|_| BloomError::AllocationFailed)?;
+        let new_filter = Box::new(BloomFilter::with_fixed_seed_counting_biased(
+            new_fp_rate,
+            new_capacity,
+            &seed,
+            self.counting_bits,
+            prior_bias,
+            false,
+        )?);
+        let memory_usage_before: usize = self.bloom_object_memory_usage();
+        self.filters.push(new_filter);
+        // If we went over capacity and scaled the vec out we need to update the memory usage by the new capacity
+        let memory_usage_after = self.bloom_object_memory_usage();
+        metrics::BLOOM_OBJECT_TOTAL_MEMORY_BYTES.fetch_add(
+            memory_usage_after - memory_usage_before,
+            std::sync::atomic::Ordering::Relaxed,
+        );
+        Ok(())
+    }
+
+    /// Add a whole batch of items in one call, amortizing two things a loop of `add_item` calls would
+    /// otherwise repeat per item: the `item_exists` scan (across every sub-filter) used to skip items
+    /// already present, and the scale-out decision once the active sub-filter fills up. Returns, in
+    /// `items` order, `1` for each item newly added and `0` for each item that already existed - either
+    /// before this call or as a duplicate already consumed earlier in the same batch, matching what
+    /// repeated `add_item` calls would report.
+    ///
+    /// The existence check is read-only and independent per item, so for a batch above
+    /// `bloom-bulk-parallel-threshold` it runs across a thread pool the same way
+    /// `add_items_parallel`/`check_items_parallel` do; the scale-out and the actual bit-setting run
+    /// serially afterwards, both because they mutate `self` and because which sub-filter a given item
+    /// lands in depends on how many earlier items in the batch already filled the ones before it.
+    pub fn add_items(
+        &mut self,
+        items: &[&[u8]],
+        validate_size_limit: bool,
+    ) -> Result<Vec<i64>, BloomError> {
+        if self.sbbf.is_some() {
+            return Err(BloomError::SbbfReadOnly);
+        }
+        if self.murmur128.is_some() || self.ribbon.is_some() {
+            // Neither backend has a scaling set of sub-filters to amortize a scale-out decision across;
+            // add one at a time, same as the serial fallback `handle_bloom_add` uses for these backends.
+            return items
+                .iter()
+                .map(|item| self.add_item(item, validate_size_limit))
+                .collect();
+        }
+        let threshold = configs::BLOOM_BULK_PARALLEL_THRESHOLD.load(Ordering::Relaxed);
+        let already_present: Vec<bool> = if items.len() as i64 > threshold {
+            let num_threads = std::thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(1);
+            let chunk_size = items.len().div_ceil(num_threads.max(1)).max(1);
+            let self_ref: &BloomObject = self;
+            std::thread::scope(|scope| {
+                items
+                    .chunks(chunk_size)
+                    .map(|chunk| {
+                        scope.spawn(move || {
+                            chunk
+                                .iter()
+                                .map(|item| self_ref.item_exists(item))
+                                .collect::<Vec<bool>>()
+                        })
+                    })
+                    .collect::<Vec<_>>()
+                    .into_iter()
+                    .flat_map(|handle| handle.join().expect("bulk add worker thread should not panic"))
+                    .collect()
+            })
+        } else {
+            items.iter().map(|item| self.item_exists(item)).collect()
+        };
+
+        let mut seen_in_batch: std::collections::HashSet<&[u8]> = std::collections::HashSet::new();
+        let new_indices: Vec<usize> = items
+            .iter()
+            .enumerate()
+            .filter(|(i, item)| !already_present[*i] && seen_in_batch.insert(**item))
+            .map(|(i, _)| i)
+            .collect();
+
+        let headroom: i64 = self
+            .filters
+            .last()
+            .map(|f| f.capacity - f.num_items)
+            .unwrap_or(0);
+        let mut remaining = new_indices.len() as i64 - headroom;
+        while remaining > 0 {
+            self.scale_out(validate_size_limit)?;
+            let added_capacity = self
+                .filters
+                .last()
+                .expect("scale_out always appends a filter")
+                .capacity;
+            remaining -= added_capacity;
+        }
+
+        let mut results = vec![0i64; items.len()];
+        let mut filter_idx = self
+            .filters
+            .iter()
+            .position(|f| f.num_items < f.capacity)
+            .unwrap_or(self.filters.len() - 1);
+        for &i in &new_indices {
+            while self.filters[filter_idx].num_items >= self.filters[filter_idx].capacity {
+                filter_idx += 1;
+            }
+            let filter = &mut self.filters[filter_idx];
+            filter.set(items[i]);
+            filter.num_items += 1;
+            results[i] = 1;
+        }
+        metrics::BLOOM_NUM_ITEMS_ACROSS_OBJECTS.fetch_add(
+            new_indices.len() as u64,
+            std::sync::atomic::Ordering::Relaxed,
+        );
+        Ok(results)
+    }
+
+    /// Returns whether `other` shares the exact same geometry as `self`: same fp_rate, tightening_ratio,
+    /// expansion, seed, and the same number/capacities of sub-filters. Only geometrically identical bloom
+    /// objects can have their underlying bitmaps OR'd together meaningfully.
+    fn is_compatible_for_merge(&self, other: &BloomObject) -> bool {
+        if self.sbbf.is_some() || other.sbbf.is_some() {
+            // Merging SBBF-backed objects would mean OR-ing their blocks together, which isn't implemented;
+            // treat them as never mergeable rather than silently no-op merging via the (empty) filters vec.
+            return false;
+        }
+        if self.murmur128.is_some() || other.murmur128.is_some() {
+            // Same reasoning as the SBBF case above - murmur128-backed objects have no sub-filters to merge.
+            return false;
+        }
+        if self.ribbon.is_some() || other.ribbon.is_some() {
+            // Same reasoning as the SBBF case above - Ribbon-backed objects have no sub-filters to merge,
+            // and a sealed Ribbon's solved matrix can't be OR-ed with another the way a bitmap can.
+            return false;
+        }
+        if self.fp_rate != other.fp_rate
+            || self.tightening_ratio != other.tightening_ratio
+            || self.expansion != other.expansion
+            || self.seed() != other.seed()
+            || self.filters.len() != other.filters.len()
+            || self.counting_bits != other.counting_bits
+        {
+            return false;
+        }
+        // Equal capacity/fp_rate doesn't guarantee equal bitmap shape: `bloom-optimize-for-memory`
+        // rounding (`size_for_capacity`) can give two otherwise-identical filters different bit lengths,
+        // and `or_bits` zips raw bitmaps byte-for-byte, so a length mismatch would silently truncate to
+        // the shorter one instead of erroring. Check the actual bitmap shape, not just the capacity that
+        // was supposed to produce it.
+        self.filters.iter().zip(other.filters.iter()).all(|(a, b)| {
+            a.capacity() == b.capacity()
+                && a.bits_total() == b.bits_total()
+                && a.number_of_hash_functions() == b.number_of_hash_functions()
+        })
+    }
+
+    /// OR the bitmaps of `other` into `self`, producing the set-union of both bloom objects.
+    /// `other` must be geometrically identical to `self` (same fp_rate, tightening_ratio, expansion, seed,
+    /// and number/capacities of sub-filters) or `BloomError::MergeFiltersIncompatible` is returned.
+    pub fn merge_from(&mut self, other: &BloomObject) -> Result<(), BloomError> {
+        if !self.is_compatible_for_merge(other) {
+            return Err(BloomError::MergeFiltersIncompatible);
+        }
+        // `union_with` re-estimates each sub-filter's `num_items` from its post-merge popcount, which can
+        // move in either direction relative to the pre-merge counts `BLOOM_NUM_ITEMS_ACROSS_OBJECTS`
+        // already reflects; fold the net change back into that global counter so it stays accurate instead
+        // of silently drifting after every merge.
+        let items_before: i64 = self.filters.iter().map(|f| f.num_items()).sum();
+        for (dest_filter, src_filter) in self.filters.iter_mut().zip(other.filters.iter()) {
+            dest_filter.union_with(src_filter);
+        }
+        let items_after: i64 = self.filters.iter().map(|f| f.num_items()).sum();
+        match items_after - items_before {
+            delta if delta > 0 => metrics::BLOOM_NUM_ITEMS_ACROSS_OBJECTS
+                .fetch_add(delta as u64, std::sync::atomic::Ordering::Relaxed),
+            delta if delta < 0 => metrics::BLOOM_NUM_ITEMS_ACROSS_OBJECTS
+                .fetch_sub((-delta) as u64, std::sync::atomic::Ordering::Relaxed),
+            _ => 0,
+        };
+        Ok(())
+    }
+
+    /// Add a batch of items to the currently active (last) sub-filter, parallelizing the hash/set work across
+    /// `num_threads` workers. Each worker hashes and sets bits for its chunk of items into a private scratch
+    /// filter sharing the active sub-filter's capacity, fp_rate, and seed; the per-chunk scratch bitmaps are
+    /// then OR'd back into the active sub-filter, which is the only point that needs synchronization since
+    /// hashing is independent per item. This keeps the main thread from stalling on very large `BF.MADD`/
+    /// `BF.INSERT ITEMS ...` payloads (see `bloom-bulk-parallel-threshold`).
+    ///
+    /// Only applies when every item fits within the active sub-filter's remaining capacity; callers should
+    /// fall back to `add_items_bulk` when a scale-out would be required.
+    pub fn add_items_parallel(
+        &mut self,
+        items: &[&[u8]],
+        num_threads: usize,
+    ) -> Result<(), BloomError> {
+        if self.sbbf.is_some() || self.murmur128.is_some() || self.ribbon.is_some() {
+            // None of SBBF, murmur128, or Ribbon-backed objects have a scaling set of sub-filters to
+            // bulk-load into; the caller (`handle_bloom_add`) falls back to `add_items_bulk` on any `Err`
+            // here, which adds murmur128 and Ribbon (unlike SBBF) items one `add_item` at a time.
+            return Err(BloomError::SbbfReadOnly);
+        }
+        let num_filters = self.filters.len() as i32;
+        let fp_rate =
+            Self::calculate_fp_rate(self.fp_rate, num_filters - 1, self.tightening_ratio)?;
+        let seed = self.seed();
+        let filter = self
+            .filters
+            .last_mut()
+            .expect("Every BloomObject is expected to have at least one filter");
+        if filter.num_items + items.len() as i64 > filter.capacity {
+            return Err(BloomError::NonScalingFilterFull);
+        }
+        let capacity = filter.capacity();
+        let chunk_size = items.len().div_ceil(num_threads.max(1)).max(1);
+        let scratch_filters: Vec<Box<BloomFilter>> = std::thread::scope(|scope| {
+            items
+                .chunks(chunk_size)
+                .map(|chunk| {
+                    scope.spawn(move || {
+                        let mut scratch =
+                            Box::new(BloomFilter::with_fixed_seed(fp_rate, capacity, &seed));
+                        for item in chunk {
+                            scratch.set(item);
+                        }
+                        scratch
+                    })
+                })
+                .collect::<Vec<_>>()
+                .into_iter()
+                .map(|handle| handle.join().expect("bulk add worker thread should not panic"))
+                .collect()
+        });
+        for scratch in &scratch_filters {
+            filter.or_bits(scratch);
+        }
+        filter.num_items += items.len() as i64;
+        metrics::BLOOM_NUM_ITEMS_ACROSS_OBJECTS.fetch_add(items.len() as u64, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Checks a batch of items for membership, splitting the work across `num_threads` workers. Unlike
+    /// `add_items_parallel`, a check never mutates the object, so every worker can run directly against the
+    /// shared `self` with no scratch filters or merge-back step - this is the read-side counterpart used to
+    /// keep large `BF.MEXISTS` calls off a single thread the same way bulk `BF.MADD`/`BF.INSERT` is. Results
+    /// are returned in the same order as `items`.
+    pub fn check_items_parallel(&self, items: &[&[u8]], num_threads: usize) -> Vec<bool> {
+        let chunk_size = items.len().div_ceil(num_threads.max(1)).max(1);
+        std::thread::scope(|scope| {
+            items
+                .chunks(chunk_size)
+                .map(|chunk| scope.spawn(move || chunk.iter().map(|item| self.item_exists(item)).collect::<Vec<bool>>()))
+                .collect::<Vec<_>>()
+                .into_iter()
+                .flat_map(|handle| handle.join().expect("bulk check worker thread should not panic"))
+                .collect()
+        })
+    }
+
+    /// Bulk-loads a whole batch of items, amortizing the hash/set work across worker threads the same way
+    /// `add_items_parallel` does for a single sub-filter - but unlike that method, this one handles a batch
+    /// that spans a scale-out boundary instead of bailing out to the serial path. After deduping against
+    /// what's already present (as `add_items` does), it grows the filter chain to hold every new item, then
+    /// for each sub-filter that ends up with new items above `bloom-bulk-parallel-threshold`, clones that
+    /// sub-filter onto `num_threads` workers, lets each insert its share of items into its own clone, and
+    /// OR's the resulting bitmaps back - mirroring `add_items_parallel`'s clone-and-union approach, just
+    /// applied to however many sub-filters the batch touches instead of only the last one. A counting-mode
+    /// object's per-slot counters are merged back via `merge_new_items_from` rather than a plain bitmap OR,
+    /// since each worker's clone starts from the sub-filter's existing counts rather than zero - see its
+    /// doc comment. Falls back to the plain batched `add_items` for batches too small to be worth the
+    /// clone overhead.
+    pub fn add_items_bulk(
+        &mut self,
+        items: &[&[u8]],
+        validate_size_limit: bool,
+    ) -> Result<Vec<i64>, BloomError> {
+        if self.sbbf.is_some() {
+            return Err(BloomError::SbbfReadOnly);
+        }
+        if self.murmur128.is_some() || self.ribbon.is_some() {
+            return items
+                .iter()
+                .map(|item| self.add_item(item, validate_size_limit))
+                .collect();
+        }
+        let threshold = configs::BLOOM_BULK_PARALLEL_THRESHOLD.load(Ordering::Relaxed);
+        if items.len() as i64 <= threshold {
+            return self.add_items(items, validate_size_limit);
+        }
+
+        let already_present: Vec<bool> = items.iter().map(|item| self.item_exists(item)).collect();
+        let mut seen_in_batch: std::collections::HashSet<&[u8]> = std::collections::HashSet::new();
+        let new_indices: Vec<usize> = items
+            .iter()
+            .enumerate()
+            .filter(|(i, item)| !already_present[*i] && seen_in_batch.insert(**item))
+            .map(|(i, _)| i)
+            .collect();
+
+        let headroom: i64 = self
+            .filters
+            .last()
+            .map(|f| f.capacity - f.num_items)
+            .unwrap_or(0);
+        let mut remaining = new_indices.len() as i64 - headroom;
+        while remaining > 0 {
+            self.scale_out(validate_size_limit)?;
+            let added_capacity = self
+                .filters
+                .last()
+                .expect("scale_out always appends a filter")
+                .capacity;
+            remaining -= added_capacity;
+        }
+
+        let mut results = vec![0i64; items.len()];
+        let mut filter_idx = self
+            .filters
+            .iter()
+            .position(|f| f.num_items < f.capacity)
+            .unwrap_or(self.filters.len() - 1);
+        let mut per_filter: Vec<Vec<usize>> = vec![Vec::new(); self.filters.len()];
+        for &i in &new_indices {
+            while self.filters[filter_idx].num_items + per_filter[filter_idx].len() as i64
+                >= self.filters[filter_idx].capacity
+            {
+                filter_idx += 1;
+            }
+            per_filter[filter_idx].push(i);
+            results[i] = 1;
+        }
+
+        let num_threads = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1);
+        for (idx, group) in per_filter.into_iter().enumerate() {
+            if group.is_empty() {
+                continue;
+            }
+            if group.len() as i64 <= threshold {
+                let filter = &mut self.filters[idx];
+                for &i in &group {
+                    filter.set(items[i]);
+                }
+                filter.num_items += group.len() as i64;
+                continue;
+            }
+            // Clone the sub-filter's current state once so every worker starts from the same bitmap
+            // (including whatever's already set), then has only its own share of new items left to insert.
+            let base_filter = BloomFilter::create_copy_from(&self.filters[idx]);
+            let chunk_size = group.len().div_ceil(num_threads.max(1)).max(1);
+            let scratch_filters: Vec<Box<BloomFilter>> = std::thread::scope(|scope| {
+                group
+                    .chunks(chunk_size)
+                    .map(|chunk| {
+                        let base_filter = &base_filter;
+                        scope.spawn(move || {
+                            let mut scratch = Box::new(BloomFilter::create_copy_from(base_filter));
+                            for &i in chunk {
+                                scratch.set(items[i]);
+                            }
+                            scratch
+                        })
+                    })
+                    .collect::<Vec<_>>()
+                    .into_iter()
+                    .map(|handle| {
+                        handle
+                            .join()
+                            .expect("bulk add worker thread should not panic")
+                    })
+                    .collect()
+            });
+            let filter = &mut self.filters[idx];
+            for scratch in &scratch_filters {
+                filter.merge_new_items_from(&base_filter, scratch);
+            }
+            filter.num_items += group.len() as i64;
+        }
+        metrics::BLOOM_NUM_ITEMS_ACROSS_OBJECTS
+            .fetch_add(new_indices.len() as u64, Ordering::Relaxed);
+        Ok(results)
+    }
+
+    /// Serializes bloomFilter to a byte array. An SBBF-backed object is tagged
+    /// `BLOOM_OBJECT_SBBF_VERSION` and its body is `ndv` and `fp_rate` followed by the filter's bitset in
+    /// the raw Apache Parquet Split Block Bloom Filter wire format - i.e. everything after the first 17
+    /// bytes is exactly what Parquet/Arrow tooling would read or write as that filter's bitset.
+    ///
+    /// A standard (non-SBBF) object's bincode-serialized body is additionally run through
+    /// `compression::compress`, which keeps the body uncompressed whenever compression wouldn't shrink
+    /// it - so a dense, mostly-full object never pays a compression penalty - and the result is tagged
+    /// `BLOOM_OBJECT_COMPRESSED_VERSION` rather than `BLOOM_OBJECT_VERSION` so `decode_object` knows to
+    /// run `compression::decompress` first.
     pub fn encode_object(&self) -> Result<Vec<u8>, BloomError> {
+        if let Some(sbbf) = &self.sbbf {
+            let bytes = sbbf.to_bytes();
+            let mut final_vec = Vec::with_capacity(17 + bytes.len());
+            final_vec.push(BLOOM_OBJECT_SBBF_VERSION);
+            final_vec.extend_from_slice(&sbbf.ndv().to_le_bytes());
+            final_vec.extend_from_slice(&self.fp_rate.to_le_bytes());
+            final_vec.extend(bytes);
+            return Ok(final_vec);
+        }
         match bincode::serialize(self) {
             Ok(vec) => {
-                let mut final_vec = Vec::with_capacity(1 + vec.len());
-                final_vec.push(BLOOM_OBJECT_VERSION);
-                final_vec.extend(vec);
+                let codec = configs::bitmap_compression_codec();
+                let compressed = compression::compress(&vec, codec);
+                let mut final_vec = Vec::with_capacity(1 + compressed.len());
+                final_vec.push(BLOOM_OBJECT_COMPRESSED_VERSION);
+                final_vec.extend(compressed);
                 Ok(final_vec)
             }
             Err(_) => Err(BloomError::EncodeBloomFilterFailed),
@@ -397,13 +1408,37 @@ impl BloomObject {
         metrics::BLOOM_NUM_OBJECTS.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
 
         metrics::BLOOM_OBJECT_TOTAL_MEMORY_BYTES.fetch_add(
-            self.bloom_object_memory_usage(),
+            self.bloom_object_memory_usage() + self.non_filter_backend_bytes(),
             std::sync::atomic::Ordering::Relaxed,
         );
     }
 
+    /// Returns the number of bitmap/solution bytes owned by an SBBF/murmur128/Ribbon backend, or `0` for
+    /// a standard scaling object (whose sub-filters self-report via `bloom_filter_incr_metrics_on_new_create`
+    /// / `Drop for BloomFilter` instead). Factored out so `bloom_object_incr_metrics_on_new_create` and
+    /// `Drop for BloomObject` stay in sync.
+    fn non_filter_backend_bytes(&self) -> usize {
+        if let Some(sbbf) = &self.sbbf {
+            return sbbf.number_of_bytes();
+        }
+        if let Some(murmur128) = &self.murmur128 {
+            return murmur128.number_of_bytes();
+        }
+        if let Some(ribbon) = &self.ribbon {
+            return ribbon.number_of_bytes();
+        }
+        0
+    }
+
     /// Deserialize a byte array to bloom filter.
     /// We will need to handle any current or previous version and deserializing the bytes into a bloom object of the running Module's current version `BLOOM_OBJECT_VERSION`.
+    ///
+    /// A leading version tag this reader doesn't natively handle is first run through
+    /// `migration::upgrade_to_known` rather than rejected outright, so a payload written by an older
+    /// module version during a rolling upgrade/downgrade gets transparently upgraded instead of forcing a
+    /// coordinated restart. A tag no registered migration recognizes - whether that's because it's newer
+    /// than this binary understands or genuinely unsupported - still fails with
+    /// `BloomError::DecodeUnsupportedVersion`, exactly as before this was wired in.
     pub fn decode_object(
         decoded_bytes: &[u8],
         validate_size_limit: bool,
@@ -411,20 +1446,75 @@ impl BloomObject {
         if decoded_bytes.is_empty() {
             return Err(BloomError::DecodeBloomFilterFailed);
         }
+        let is_natively_supported = |version: u8| {
+            matches!(
+                version,
+                BLOOM_OBJECT_VERSION | BLOOM_OBJECT_COMPRESSED_VERSION | BLOOM_OBJECT_SBBF_VERSION
+            )
+        };
+        let upgraded;
+        let decoded_bytes = if is_natively_supported(decoded_bytes[0]) {
+            decoded_bytes
+        } else {
+            upgraded = migration::upgrade_to_known(decoded_bytes, is_natively_supported)?;
+            upgraded.as_slice()
+        };
         let version = decoded_bytes[0];
         match version {
-            1 => {
+            BLOOM_OBJECT_VERSION | BLOOM_OBJECT_COMPRESSED_VERSION => {
+                // Version 1 payloads are raw bincode; `BLOOM_OBJECT_COMPRESSED_VERSION` payloads are the
+                // same bincode shape wrapped by `compression::compress` in `encode_object`, so unwrap that
+                // layer first and otherwise share the exact same deserialize-and-validate path below.
+                let body = if version == BLOOM_OBJECT_COMPRESSED_VERSION {
+                    compression::decompress(&decoded_bytes[1..])?
+                } else {
+                    decoded_bytes[1..].to_vec()
+                };
                 // Always use new version to initialize a BloomObject.
                 // This is to ensure that the new fields can be recognized when the object is serialized and deserialized in the future.
-                let (expansion, fp_rate, tightening_ratio, is_seed_random, filters): (
+                //
+                // `bincode::deserialize` applies no allocation limit by default, so a corrupt or hostile
+                // `RESTORE` payload claiming a huge `Vec<u8>`/`Vec<Box<BloomFilter>>` length could make the
+                // allocator abort the whole process before the read loop ever notices the input is too short
+                // to back it. Capping the deserializer's budget at the actual input length (via
+                // `bincode::Options::with_limit`, rather than the infallible `try_reserve_exact` used in
+                // `new_reserved`'s own allocations, since this allocation happens inside the vendored
+                // `bincode`/`bloomfilter` crates) turns that into an ordinary `DecodeBloomFilterFailed`.
+                #[allow(clippy::type_complexity)]
+                let (
+                    expansion,
+                    fp_rate,
+                    tightening_ratio,
+                    is_seed_random,
+                    filters,
+                    counting_bits,
+                    sbbf,
+                    murmur128,
+                    ribbon,
+                ): (
                     u32,
                     f64,
                     f64,
                     bool,
                     Vec<Box<BloomFilter>>,
-                ) = match bincode::deserialize::<(u32, f64, f64, bool, Vec<Box<BloomFilter>>)>(
-                    &decoded_bytes[1..],
-                ) {
+                    Option<u8>,
+                    Option<Box<SplitBlockFilter>>,
+                    Option<Box<Murmur128Filter>>,
+                    Option<Box<RibbonFilter>>,
+                ) = match bincode::options()
+                    .with_limit(body.len() as u64)
+                    .deserialize::<(
+                        u32,
+                        f64,
+                        f64,
+                        bool,
+                        Vec<Box<BloomFilter>>,
+                        Option<u8>,
+                        Option<Box<SplitBlockFilter>>,
+                        Option<Box<Murmur128Filter>>,
+                        Option<Box<RibbonFilter>>,
+                    )>(&body)
+                {
                     Ok(values) => {
                         // Add individual bloom filter metrics.
                         for filter in &values.4 {
@@ -464,13 +1554,65 @@ impl BloomObject {
                     tightening_ratio,
                     is_seed_random,
                     filters,
+                    counting_bits,
+                    sbbf,
+                    murmur128,
+                    ribbon,
                 };
                 // Add overall bloom object metrics.
                 item.bloom_object_incr_metrics_on_new_create();
                 let bytes = item.memory_usage();
                 // Reject the request, if the operation will result in creation of a bloom object of size greater than what is allowed.
-                if validate_size_limit && !BloomObject::validate_size(bytes) {
-                    return Err(BloomError::ExceedsMaxBloomSize);
+                if validate_size_limit {
+                    if !BloomObject::validate_size(bytes) {
+                        return Err(BloomError::ExceedsMaxBloomSize);
+                    }
+                    // `bytes` is already reflected in the running total via the `bloom_object_incr_metrics_on_new_create`
+                    // call above, so check the budget with no further marginal addition.
+                    if !BloomObject::validate_global_memory_budget(0) {
+                        return Err(BloomError::ExceedsGlobalMemoryBudget);
+                    }
+                }
+                Ok(item)
+            }
+            BLOOM_OBJECT_SBBF_VERSION => {
+                if decoded_bytes.len() < 17 {
+                    return Err(BloomError::DecodeBloomFilterFailed);
+                }
+                let ndv = i64::from_le_bytes(
+                    decoded_bytes[1..9]
+                        .try_into()
+                        .expect("slice of length 8 always converts"),
+                );
+                let fp_rate = f64::from_le_bytes(
+                    decoded_bytes[9..17]
+                        .try_into()
+                        .expect("slice of length 8 always converts"),
+                );
+                if !(fp_rate > BLOOM_FP_RATE_MIN && fp_rate < BLOOM_FP_RATE_MAX) {
+                    return Err(BloomError::ErrorRateRange);
+                }
+                let sbbf = SplitBlockFilter::from_bytes(&decoded_bytes[17..], ndv)?;
+                let item = BloomObject {
+                    expansion: 0,
+                    fp_rate,
+                    tightening_ratio: 1.0,
+                    is_seed_random: true,
+                    filters: Vec::new(),
+                    counting_bits: None,
+                    sbbf: Some(Box::new(sbbf)),
+                    murmur128: None,
+                    ribbon: None,
+                };
+                item.bloom_object_incr_metrics_on_new_create();
+                let bytes = item.memory_usage();
+                if validate_size_limit {
+                    if !BloomObject::validate_size(bytes) {
+                        return Err(BloomError::ExceedsMaxBloomSize);
+                    }
+                    if !BloomObject::validate_global_memory_budget(0) {
+                        return Err(BloomError::ExceedsGlobalMemoryBudget);
+                    }
                 }
                 Ok(item)
             }
@@ -478,6 +1620,63 @@ impl BloomObject {
         }
     }
 
+    /// Returns the number of chunks `BF.SCANDUMP` will emit for this object: one header chunk followed
+    /// by one chunk per sub-filter, so a scaled-out object can be streamed and reassembled one sub-filter
+    /// at a time instead of as a single monolithic blob.
+    pub fn num_scandump_chunks(&self) -> usize {
+        1 + self.filters.len()
+    }
+
+    /// Serializes the `chunk_idx`'th `BF.SCANDUMP` chunk of this object. Chunk `0` is the header
+    /// (expansion, fp_rate, tightening_ratio, is_seed_random and the number of sub-filters to expect);
+    /// chunk `i` for `i` in `1..=num_scandump_chunks() - 1` is the `i`th sub-filter's serialized bitmap.
+    pub fn encode_scandump_chunk(&self, chunk_idx: usize) -> Result<Vec<u8>, BloomError> {
+        if chunk_idx == 0 {
+            let header = ScandumpHeader {
+                expansion: self.expansion,
+                fp_rate: self.fp_rate,
+                tightening_ratio: self.tightening_ratio,
+                is_seed_random: self.is_seed_random,
+                num_filters: self.filters.len(),
+                counting_bits: self.counting_bits,
+            };
+            return bincode::serialize(&header).map_err(|_| BloomError::EncodeBloomFilterFailed);
+        }
+        // Sub-filter chunks are bitmap-dominated, so they are the ones worth compressing; the tiny
+        // header chunk is left as-is. See `bloom-bitmap-compression` / `compression::compress`.
+        let bytes = bincode::serialize(&self.filters[chunk_idx - 1])
+            .map_err(|_| BloomError::EncodeBloomFilterFailed)?;
+        let codec = configs::bitmap_compression_codec();
+        Ok(compression::compress(&bytes, codec))
+    }
+
+    /// Deserializes a `BF.LOADCHUNK` header chunk (chunk 0 of a `BF.SCANDUMP` stream).
+    #[allow(clippy::type_complexity)]
+    pub fn decode_scandump_header(
+        bytes: &[u8],
+    ) -> Result<(u32, f64, f64, bool, usize, Option<u8>), BloomError> {
+        bincode::deserialize::<ScandumpHeader>(bytes)
+            .map(|h| {
+                (
+                    h.expansion,
+                    h.fp_rate,
+                    h.tightening_ratio,
+                    h.is_seed_random,
+                    h.num_filters,
+                    h.counting_bits,
+                )
+            })
+            .map_err(|_| BloomError::DecodeBloomFilterFailed)
+    }
+
+    /// Deserializes a single sub-filter `BF.LOADCHUNK` chunk (chunk `1..=num_filters` of a `BF.SCANDUMP`
+    /// stream) back into a `BloomFilter`.
+    pub fn decode_scandump_filter_chunk(bytes: &[u8]) -> Result<Box<BloomFilter>, BloomError> {
+        let bytes = compression::decompress(bytes)?;
+        bincode::deserialize::<Box<BloomFilter>>(&bytes)
+            .map_err(|_| BloomError::DecodeBloomFilterFailed)
+    }
+
     /// This method is called from two different bloom commands: BF.INFO and BF.INSERT. The functionality varies slightly on which command it
     /// is called from. When called from BF.INFO, this method is used to find the maximum possible size that the bloom object could scale to
     /// without throwing an error. When called from BF.INSERT, this method is used to determine if it is possible to reach the provided `validate_scale_to`.
@@ -502,6 +1701,7 @@ impl BloomObject {
         validate_scale_to: i64,
         tightening_ratio: f64,
         expansion: u32,
+        counting_bits: Option<u8>,
     ) -> Result<i64, BloomError> {
         let mut curr_filter_capacity = capacity;
         let mut curr_total_capacity = 0;
@@ -523,7 +1723,8 @@ impl BloomObject {
                 }
             };
             // Check that if it scales to this number of filters that the object won't exceed the memory limit
-            let curr_filter_size = BloomFilter::compute_size(curr_filter_capacity, curr_fp_rate);
+            let curr_filter_size =
+                BloomFilter::compute_size(curr_filter_capacity, curr_fp_rate, counting_bits);
             // The capacity is always a power of two above or equal to the size other than for vectors of size 1 where the capacity is 1 and for size 2 where the
             // capacity of the vec is 4.
             let curr_object_size = BloomObject::compute_size(if curr_num_filters == 0 {
@@ -570,6 +1771,25 @@ pub struct BloomFilter {
     bloom: Box<bloomfilter::Bloom<[u8]>>,
     num_items: i64,
     capacity: i64,
+    /// One saturating counter per bit in `bloom`'s bitmap, present only when this filter was created in
+    /// `COUNTING` mode. A bit stays set in `bloom` for as long as its counter here is non-zero, so
+    /// `check`/`bits_set` keep working unmodified; `set` increments and `delete` decrements these counters
+    /// and updates `bloom`'s bitmap in place to match. See `BloomObject::delete_item` / `BF.DEL`.
+    counters: Option<Vec<u8>>,
+    /// Counter width in bits this filter was created with (e.g. `4` by default), used only to compute the
+    /// saturation ceiling `(1 << counting_bits) - 1`. `None` for a plain (non-counting) filter.
+    counting_bits: Option<u8>,
+    /// Set once any counter in `counters` has hit its saturation ceiling. A saturated counter can never be
+    /// decremented back to an exact value, so once this is `true` a `BF.DEL` on this filter may leave stale
+    /// bits set or clear bits still shared by another item.
+    counting_saturated: bool,
+    /// Signed bit-count difference between this filter's actual bitmap allocation and the size implied by
+    /// its `capacity`/fp rate alone. Zero unless `bloom-optimize-for-memory` was enabled at construction
+    /// time, in which case it is positive when sizing rounded up to reclaim jemalloc's allocation slack, or
+    /// zero when it rounded down to keep the chain's average bits-per-key on target. `BloomObject`
+    /// sums this across `filters` to pick the next sibling's rounding direction. See
+    /// `BloomFilter::size_for_capacity`.
+    mem_rounding_bias: i64,
 }
 
 pub fn deserialize_boxed_bloom<'de, D>(deserializer: D) -> Result<Box<Bloom<[u8]>>, D::Error>
@@ -579,34 +1799,217 @@ where
     deserialize(deserializer).map(Box::new)
 }
 
+/// Probes how many bytes jemalloc would actually grant for a `requested_bytes` allocation. Allocators
+/// round up to fixed size classes, so a filter that asks for exactly `requested_bytes` may occupy
+/// noticeably more with that slack going to waste; `BloomFilter::size_for_capacity` uses this to decide
+/// whether reclaiming it is worthwhile. Allocates and immediately drops a scratch buffer purely to read
+/// back its real usable size via `malloc_usable_size`.
+fn usable_bitmap_bytes(requested_bytes: usize) -> usize {
+    if requested_bytes == 0 {
+        return 0;
+    }
+    let scratch: Vec<u8> = Vec::with_capacity(requested_bytes);
+    let usable = unsafe { libc::malloc_usable_size(scratch.as_ptr() as *mut libc::c_void) };
+    usable.max(requested_bytes)
+}
+
+/// Probes whether a `bits`-bit bitmap can actually be allocated. `bloomfilter::Bloom::new`/
+/// `new_with_seed` allocate their backing `Vec` infallibly and abort the process if the allocator can't
+/// satisfy the request, so this runs first and turns a capacity overflow or allocator failure into a
+/// recoverable `BloomError::AllocationFailed` instead. The probed buffer is dropped immediately; the
+/// caller still needs to make its own (now very likely to succeed) allocation afterwards.
+fn try_reserve_bitmap(bits: u64) -> Result<(), BloomError> {
+    let bytes: usize = bits
+        .div_ceil(8)
+        .try_into()
+        .map_err(|_| BloomError::AllocationFailed)?;
+    let mut probe: Vec<u8> = Vec::new();
+    probe
+        .try_reserve_exact(bytes)
+        .map_err(|_| BloomError::AllocationFailed)
+}
+
 impl BloomFilter {
     /// Instantiate empty BloomFilter object with a fixed seed used to create sip keys.
     pub fn with_fixed_seed(fp_rate: f64, capacity: i64, fixed_seed: &[u8; 32]) -> BloomFilter {
+        // Fallible allocation is only threaded through the `BF.RESERVE`/`BF.ADD`/`BF.INSERT` paths
+        // (`BloomObject::new_reserved*`/`add_item`); this plain constructor is used for scratch buffers
+        // and cascade levels sized well under the per-object limit, so it keeps the infallible contract.
+        Self::with_fixed_seed_counting(fp_rate, capacity, fixed_seed, None)
+            .expect("We expect bloomfilter::Bloom<[u8]> creation to succeed")
+    }
+
+    /// Instantiate empty BloomFilter object with a fixed seed, optionally in `COUNTING` mode. When
+    /// `counting_bits` is `Some(width)`, every hashed slot carries a `width`-bit saturating counter
+    /// alongside the plain bitmap, enabling `BF.DEL`. Returns `BloomError::AllocationFailed` instead of
+    /// aborting the process if the backing bitmap can't be allocated.
+    pub fn with_fixed_seed_counting(
+        fp_rate: f64,
+        capacity: i64,
+        fixed_seed: &[u8; 32],
+        counting_bits: Option<u8>,
+    ) -> Result<BloomFilter, BloomError> {
+        Self::with_fixed_seed_counting_biased(fp_rate, capacity, fixed_seed, counting_bits, 0, false)
+    }
+
+    /// Like `with_fixed_seed_counting`, but also takes `prior_bias` - the net bits-per-key
+    /// surplus/shortfall already accumulated by earlier sibling filters in the same `BloomObject` - so
+    /// this filter's own size can be chosen to pull that running total back toward zero. Used by
+    /// `BloomObject` scale-out; plain callers go through `with_fixed_seed_counting` with `prior_bias: 0`.
+    /// See `size_for_capacity`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn with_fixed_seed_counting_biased(
+        fp_rate: f64,
+        capacity: i64,
+        fixed_seed: &[u8; 32],
+        counting_bits: Option<u8>,
+        prior_bias: i64,
+        pow2_sizing: bool,
+    ) -> Result<BloomFilter, BloomError> {
+        let (bits, mem_rounding_bias) =
+            Self::size_for_capacity(capacity, fp_rate, prior_bias, pow2_sizing);
+        try_reserve_bitmap(bits)?;
         let bloom =
-            bloomfilter::Bloom::new_for_fp_rate_with_seed(capacity as usize, fp_rate, fixed_seed)
+            bloomfilter::Bloom::new_with_seed((bits / 8) as usize, capacity as usize, fixed_seed)
                 .expect("We expect bloomfilter::Bloom<[u8]> creation to succeed");
+        let counters = match counting_bits {
+            Some(_) => {
+                let mut counters = Vec::new();
+                counters
+                    .try_reserve_exact(bloom.len() as usize)
+                    .map_err(|_| BloomError::AllocationFailed)?;
+                counters.resize(bloom.len() as usize, 0u8);
+                Some(counters)
+            }
+            None => None,
+        };
         let fltr = BloomFilter {
             bloom: Box::new(bloom),
             num_items: 0,
             capacity,
+            counters,
+            counting_bits,
+            counting_saturated: false,
+            mem_rounding_bias,
         };
         fltr.bloom_filter_incr_metrics_on_new_create();
-        fltr
+        Ok(fltr)
     }
 
     /// Instantiate empty BloomFilter object with a randomly generated seed used to create sip keys.
     pub fn with_random_seed(fp_rate: f64, capacity: i64) -> BloomFilter {
+        // See the comment on `with_fixed_seed` - this plain constructor keeps the infallible contract.
+        Self::with_random_seed_counting(fp_rate, capacity, None)
+            .expect("We expect bloomfilter::Bloom<[u8]> creation to succeed")
+    }
+
+    /// Instantiate empty BloomFilter object with a randomly generated seed, optionally in `COUNTING`
+    /// mode. See `with_fixed_seed_counting`.
+    pub fn with_random_seed_counting(
+        fp_rate: f64,
+        capacity: i64,
+        counting_bits: Option<u8>,
+    ) -> Result<BloomFilter, BloomError> {
+        Self::with_random_seed_counting_biased(fp_rate, capacity, counting_bits, 0, false)
+    }
+
+    /// Like `with_random_seed_counting`, but also takes `prior_bias`. See
+    /// `with_fixed_seed_counting_biased`.
+    pub fn with_random_seed_counting_biased(
+        fp_rate: f64,
+        capacity: i64,
+        counting_bits: Option<u8>,
+        prior_bias: i64,
+        pow2_sizing: bool,
+    ) -> Result<BloomFilter, BloomError> {
+        let (bits, mem_rounding_bias) =
+            Self::size_for_capacity(capacity, fp_rate, prior_bias, pow2_sizing);
+        try_reserve_bitmap(bits)?;
         let bloom = Box::new(
-            bloomfilter::Bloom::new_for_fp_rate(capacity as usize, fp_rate)
+            bloomfilter::Bloom::new((bits / 8) as usize, capacity as usize)
                 .expect("We expect bloomfilter::Bloom<[u8]> creation to succeed"),
         );
+        let counters = match counting_bits {
+            Some(_) => {
+                let mut counters = Vec::new();
+                counters
+                    .try_reserve_exact(bloom.len() as usize)
+                    .map_err(|_| BloomError::AllocationFailed)?;
+                counters.resize(bloom.len() as usize, 0u8);
+                Some(counters)
+            }
+            None => None,
+        };
         let fltr = BloomFilter {
             bloom,
             num_items: 0,
             capacity,
+            counters,
+            counting_bits,
+            counting_saturated: false,
+            mem_rounding_bias,
         };
         fltr.bloom_filter_incr_metrics_on_new_create();
-        fltr
+        Ok(fltr)
+    }
+
+    /// Opt-in constructor that rounds the backing bitmap's bit length up to the next power of two (see
+    /// `size_for_capacity`'s `pow2_sizing` parameter) instead of the exact `compute_bitmap_size` value.
+    /// Reserve-time flag for callers that want to pin a filter's shape ahead of a future mask-based
+    /// (`hash & (bits_total - 1)`) index lookup; existing reservations are unaffected since every other
+    /// constructor passes `pow2_sizing: false`.
+    pub fn with_random_seed_counting_pow2(
+        fp_rate: f64,
+        capacity: i64,
+        counting_bits: Option<u8>,
+    ) -> Result<BloomFilter, BloomError> {
+        Self::with_random_seed_counting_biased(fp_rate, capacity, counting_bits, 0, true)
+    }
+
+    /// Decides how many bits to actually allocate for a new filter sized for `capacity` items at
+    /// `fp_rate`, given `prior_bias` (see `mem_rounding_bias`). With `bloom-optimize-for-memory` off,
+    /// this is just the bit count `compute_bitmap_size` implies. With it on: probes
+    /// `usable_bitmap_bytes` for the allocator's actual size class and, if that leaves slack, rounds up
+    /// to use it (reclaiming the slack rather than wasting it) whenever `prior_bias` isn't already in
+    /// surplus, and rounds down (ignores the slack) otherwise - alternating so the chain's average
+    /// bits-per-key tracks `fp_rate` instead of drifting every sub-filter rounds up. Returns the chosen
+    /// bit count and this filter's own signed contribution to the running bias.
+    ///
+    /// When `pow2_sizing` is set, the bit count is instead rounded up to the next power of two and the
+    /// `bloom-optimize-for-memory` slack logic is skipped entirely - the two rounding strategies aren't
+    /// compatible, since the bias-averaging above assumes byte-granularity rounding. A power-of-two bit
+    /// count is a prerequisite for replacing `hash % bits_total` with `hash & (bits_total - 1)` in the
+    /// index iterator, but that iterator lives in the vendored `bloomfilter` crate, which this tree
+    /// doesn't fork; `pow2_sizing` only gets the bitmap to the right shape for that change, not the
+    /// lookup itself. See `with_random_seed_counting_pow2`.
+    fn size_for_capacity(
+        capacity: i64,
+        fp_rate: f64,
+        prior_bias: i64,
+        pow2_sizing: bool,
+    ) -> (u64, i64) {
+        let requested_bytes =
+            bloomfilter::Bloom::<[u8]>::compute_bitmap_size(capacity as usize, fp_rate);
+        let requested_bits = (requested_bytes as u64) * 8;
+        if pow2_sizing {
+            return (requested_bits.next_power_of_two(), 0);
+        }
+        if !configs::optimize_for_memory() {
+            return (requested_bits, 0);
+        }
+        let usable_bits = usable_bitmap_bytes(requested_bytes) as u64 * 8;
+        let slack = usable_bits.saturating_sub(requested_bits) as i64;
+        if slack <= 0 || prior_bias > 0 {
+            (requested_bits, 0)
+        } else {
+            (usable_bits, slack)
+        }
+    }
+
+    /// Returns this filter's signed contribution to its `BloomObject`'s rounding bias. See
+    /// `mem_rounding_bias` / `size_for_capacity`.
+    pub fn mem_rounding_bias(&self) -> i64 {
+        self.mem_rounding_bias
     }
 
     /// Create a new BloomFilter from dumped information (RDB load).
@@ -618,6 +2021,12 @@ impl BloomFilter {
             bloom: Box::new(bloom),
             num_items,
             capacity,
+            counters: None,
+            counting_bits: None,
+            counting_saturated: false,
+            // A restored filter's bitmap already reflects whatever rounding decision produced it; the
+            // running bias itself isn't persisted and simply restarts at zero for the next scale-out.
+            mem_rounding_bias: 0,
         };
         fltr.bloom_filter_incr_metrics_on_new_create();
         metrics::BLOOM_NUM_ITEMS_ACROSS_OBJECTS
@@ -627,7 +2036,12 @@ impl BloomFilter {
 
     /// Create a new BloomFilter from an existing BloomFilter object (COPY command).
     pub fn create_copy_from(bf: &BloomFilter) -> BloomFilter {
-        BloomFilter::from_existing(&bf.bloom.to_bytes(), bf.num_items, bf.capacity)
+        let mut copy = BloomFilter::from_existing(&bf.bloom.to_bytes(), bf.num_items, bf.capacity);
+        copy.counters.clone_from(&bf.counters);
+        copy.counting_bits = bf.counting_bits;
+        copy.counting_saturated = bf.counting_saturated;
+        copy.mem_rounding_bias = bf.mem_rounding_bias;
+        copy
     }
 
     fn bloom_filter_incr_metrics_on_new_create(&self) {
@@ -668,28 +2082,322 @@ impl BloomFilter {
         std::mem::size_of::<BloomFilter>()
             + std::mem::size_of::<bloomfilter::Bloom<[u8]>>()
             + (self.bloom.len() / 8) as usize
+            + self.counters.as_ref().map_or(0, |c| c.len())
+    }
+
+    /// Returns the number of bits currently set in the bitmap (population count). Used for fill-ratio
+    /// introspection (`BF.INFO ... MEMORY`) and the popcount-based cardinality estimator.
+    pub fn bits_set(&self) -> u64 {
+        self.bloom
+            .as_slice()
+            .iter()
+            .map(|byte| byte.count_ones() as u64)
+            .sum()
+    }
+
+    /// Returns the total number of bits in the bitmap.
+    pub fn bits_total(&self) -> u64 {
+        self.bloom.len()
+    }
+
+    /// Returns the number of hash functions (`k`) this filter's bitmap was built with. Alongside
+    /// `bits_total`, this is what `merge_from` checks pairwise to make sure two sub-filters' bitmaps are
+    /// actually the same shape before OR-ing them byte-for-byte - same `capacity`/`fp_rate` alone isn't
+    /// enough, since `bloom-optimize-for-memory` rounding can give otherwise-identical filters different
+    /// bitmap lengths (see `size_for_capacity`).
+    pub fn number_of_hash_functions(&self) -> u32 {
+        self.bloom.number_of_hash_functions()
+    }
+
+    /// Returns the fraction of bits currently set, i.e. `bits_set() / bits_total()`. Used for
+    /// `BF.INFO ... FILLRATIO`/`FILTERSDETAIL`.
+    pub fn fill_ratio(&self) -> f64 {
+        let total = self.bits_total();
+        if total == 0 {
+            return 0.0;
+        }
+        self.bits_set() as f64 / total as f64
+    }
+
+    /// Estimates the realized false-positive probability from the filter's current bit-fill ratio and hash
+    /// count: `(bits_set/bits_total)^k`. Unlike `fp_rate`, which is the configured design target, this
+    /// reflects the filter's actual state and degrades toward 1 as it approaches saturation, letting an
+    /// operator see a scaling filter is overdue to expand (or a NONSCALING one has outgrown its budget)
+    /// before lookups actually start returning more false positives than intended.
+    pub fn current_error_rate(&self) -> f64 {
+        self.fill_ratio().powi(self.bloom.number_of_hash_functions() as i32)
     }
 
     /// Calculates the number of bytes that the bloom filter will require to be allocated.
-    pub fn compute_size(capacity: i64, fp_rate: f64) -> usize {
-        std::mem::size_of::<BloomFilter>()
+    pub fn compute_size(capacity: i64, fp_rate: f64, counting_bits: Option<u8>) -> usize {
+        let bitmap_bytes = bloomfilter::Bloom::<[u8]>::compute_bitmap_size(capacity as usize, fp_rate);
+        let mut size = std::mem::size_of::<BloomFilter>()
             + std::mem::size_of::<bloomfilter::Bloom<[u8]>>()
-            + bloomfilter::Bloom::<[u8]>::compute_bitmap_size(capacity as usize, fp_rate)
+            + bitmap_bytes;
+        if counting_bits.is_some() {
+            // `counters` holds one `u8` per bit of the bitmap (see `with_fixed_seed_counting_biased`),
+            // so a COUNTING filter's footprint is the bitmap plus a counters byte array eight times its size.
+            size += bitmap_bytes * 8;
+        }
+        size
     }
 
+    /// Tests the bits `hash_indices` maps `item` to directly against the raw bitmap, rather than going
+    /// through `self.bloom.check`: `set` below drives the same bitmap through the exact same indices, so
+    /// testing those indices here is what keeps `check` consistent with what `set`/`delete` actually did,
+    /// instead of relying on two independently-implemented hash schemes agreeing by coincidence.
     pub fn check(&self, item: &[u8]) -> bool {
-        self.bloom.check(item)
+        let bytes = self.bloom.as_slice();
+        self.hash_indices(item)
+            .iter()
+            .all(|&idx| bytes[idx / 8] & (1 << (idx % 8)) != 0)
+    }
+
+    /// Returns a mutable view directly into the bytes `self.bloom` already owns, without copying or
+    /// reallocating. `bloomfilter::Bloom` only exposes the bitmap read-only via `as_slice`, but `&mut self`
+    /// here guarantees no other reference to `self.bloom` exists, and this never resizes or replaces its
+    /// backing allocation - only flips individual bits in place - so reinterpreting the pointer `as_slice`
+    /// returns as mutable is sound. Lets `set`/`delete`/`or_bits` touch exactly the bytes they need to
+    /// (O(k), or O(bitmap_size) for a whole-filter merge) instead of copying the bitmap out to rebuild a
+    /// patched `Bloom` via `from_slice`, which this used to do on every call.
+    fn bitmap_mut(&mut self) -> &mut [u8] {
+        let slice = self.bloom.as_slice();
+        let ptr = slice.as_ptr() as *mut u8;
+        let len = slice.len();
+        // SAFETY: `ptr`/`len` describe `self.bloom`'s own live allocation as returned by `as_slice`;
+        // `&mut self` means nothing else can be reading or writing it concurrently, and we only ever flip
+        // bits through the returned slice, never move or resize the allocation it points into.
+        unsafe { std::slice::from_raw_parts_mut(ptr, len) }
     }
 
     pub fn set(&mut self, item: &[u8]) {
-        self.bloom.set(item)
+        let indices = self.hash_indices(item);
+        let bytes = self.bitmap_mut();
+        for &idx in &indices {
+            bytes[idx / 8] |= 1 << (idx % 8);
+        }
+        if self.counters.is_none() {
+            return;
+        }
+        let ceiling = self.counting_ceiling();
+        let mut saturated = false;
+        let counters = self
+            .counters
+            .as_mut()
+            .expect("just checked counters is Some");
+        for idx in indices {
+            if let Some(counter) = counters.get_mut(idx) {
+                if *counter >= ceiling {
+                    saturated = true;
+                } else {
+                    *counter += 1;
+                }
+            }
+        }
+        if saturated {
+            self.counting_saturated = true;
+        }
+    }
+
+    /// Returns whether this filter was created with `COUNTING` enabled.
+    pub fn is_counting(&self) -> bool {
+        self.counting_bits.is_some()
+    }
+
+    /// Returns whether any counter has ever hit `counting_ceiling()`, making a subsequent `BF.DEL` on this
+    /// filter unreliable (see `counters` doc comment).
+    pub fn counting_saturated(&self) -> bool {
+        self.counting_saturated
+    }
+
+    /// The highest value a counter can hold before it saturates: `(1 << counting_bits) - 1`, or `0` for a
+    /// non-counting filter.
+    fn counting_ceiling(&self) -> u8 {
+        match self.counting_bits {
+            Some(bits) => ((1u16 << bits) - 1) as u8,
+            None => 0,
+        }
+    }
+
+    /// Decrements the counter at every slot `item` hashes to, clearing the corresponding bitmap bit once
+    /// its counter reaches zero so `check` keeps reflecting only items that are still present. Returns
+    /// `BloomError::NotACountingFilter` if this filter was not created with `COUNTING`.
+    pub fn delete(&mut self, item: &[u8]) -> Result<(), BloomError> {
+        if self.counters.is_none() {
+            return Err(BloomError::NotACountingFilter);
+        }
+        let indices = self.hash_indices(item);
+        let mut newly_cleared = Vec::new();
+        {
+            let counters = self
+                .counters
+                .as_mut()
+                .expect("just checked counters is Some");
+            for &idx in &indices {
+                if let Some(counter) = counters.get_mut(idx) {
+                    if *counter > 0 {
+                        *counter -= 1;
+                        if *counter == 0 {
+                            newly_cleared.push(idx);
+                        }
+                    }
+                }
+            }
+        }
+        // A bit stays set for as long as its counter is non-zero, so the only bits that can possibly need
+        // clearing are the ones whose counter just reached zero above - flip exactly those in place
+        // rather than rebuilding the whole bitmap from every counter.
+        if !newly_cleared.is_empty() {
+            let bytes = self.bitmap_mut();
+            for idx in newly_cleared {
+                bytes[idx / 8] &= !(1 << (idx % 8));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the bitmap index for each of the raw bloom's `k` hash functions for `item`, using enhanced
+    /// double hashing (`h1 + i*h2`, per Kirsch/Mitzenmacher) seeded from the filter's own seed. This is
+    /// the one and only index computation `BloomFilter` uses - `check`/`set`/`delete` all call it and
+    /// operate on the raw bitmap (and, for `COUNTING` filters, `counters`) at exactly the indices it
+    /// returns, rather than going through the vendored `bloomfilter::Bloom`'s own internal (and otherwise
+    /// inaccessible) hashing. That's deliberate: two independently-implemented hash constructions have no
+    /// reason to ever agree on an index sequence, so the bitmap and the counters must be driven by the
+    /// same function to stay in sync.
+    ///
+    /// `m` need not be a power of two, so a candidate is never folded down with `% m`: that biases the
+    /// low indices whenever `m` isn't a power of two, which in turn raises the real false-positive rate
+    /// above what `m`/`k` were chosen for. Instead each candidate is masked into the smallest
+    /// power-of-two range `2^ceil(log2(m))` and rejected - redrawn from a fresh hash of `(seed, item, i,
+    /// attempt)` - until it lands below `m`. When `m` is already a power of two the first candidate is
+    /// always below `m`, so the rejection loop never runs and this costs nothing in that case.
+    fn hash_indices(&self, item: &[u8]) -> Vec<usize> {
+        use std::hash::{Hash, Hasher};
+        let m = self.bloom.len().max(1);
+        let k = self.bloom.number_of_hash_functions() as u64;
+        let seed = self.bloom.seed();
+        let mut hasher1 = std::collections::hash_map::DefaultHasher::new();
+        hasher1.write(&seed[0..16]);
+        item.hash(&mut hasher1);
+        let h1 = hasher1.finish();
+        let mut hasher2 = std::collections::hash_map::DefaultHasher::new();
+        hasher2.write(&seed[16..32]);
+        item.hash(&mut hasher2);
+        let h2 = hasher2.finish();
+        let mask = m.next_power_of_two() - 1;
+        (0..k)
+            .map(|i| {
+                let mut candidate = h1.wrapping_add(i.wrapping_mul(h2)) & mask;
+                let mut attempt: u64 = 0;
+                while candidate >= m {
+                    attempt += 1;
+                    let mut rehash = std::collections::hash_map::DefaultHasher::new();
+                    rehash.write(&seed[0..16]);
+                    item.hash(&mut rehash);
+                    rehash.write(&i.to_le_bytes());
+                    rehash.write(&attempt.to_le_bytes());
+                    candidate = rehash.finish() & mask;
+                }
+                candidate as usize
+            })
+            .collect()
+    }
+
+    /// OR the raw bitmap of `other` into `self`, leaving `num_items` untouched. Callers must have already
+    /// validated that `self` and `other` share the same bitmap length (i.e. same capacity/fp_rate/seed)
+    /// since the bitmaps are OR'd byte-for-byte, in place (see `bitmap_mut`) rather than built up in a
+    /// scratch `Vec` and rebuilt through `Bloom::from_slice` the way this used to.
+    fn or_bits(&mut self, other: &BloomFilter) {
+        let other_bytes = other.bloom.as_slice();
+        let bytes = self.bitmap_mut();
+        for (byte, other_byte) in bytes.iter_mut().zip(other_bytes.iter()) {
+            *byte |= other_byte;
+        }
+    }
+
+    /// Estimates the number of distinct items represented by the filter's current bitmap from the classic
+    /// bloom filter cardinality estimator `n ≈ -(m/k) * ln(1 - X/m)`, where `m` is the number of bits, `k`
+    /// is the number of hash functions, and `X` is the number of bits currently set (a popcount over the
+    /// raw bitmap). Used after a merge, where the true post-union count can no longer be derived from the
+    /// two source counts since the same bit may have been set by more than one source.
+    pub fn estimated_cardinality(&self) -> i64 {
+        let m = self.bits_total() as f64;
+        let k = self.bloom.number_of_hash_functions() as f64;
+        let x = self.bits_set() as f64;
+        if m <= 0.0 || k <= 0.0 || x >= m {
+            return self.capacity;
+        }
+        (-(m / k) * (1.0 - x / m).ln()).round() as i64
+    }
+
+    /// OR the raw bitmap of `other` into `self`, producing the set-union of both filters. When both
+    /// filters are in `COUNTING` mode, their counters are saturating-summed slot-by-slot too, so a
+    /// subsequent `BF.DEL` on the merged filter still has accurate counts to decrement instead of
+    /// silently clearing bits the merge didn't actually own.
+    pub fn union_with(&mut self, other: &BloomFilter) {
+        self.or_bits(other);
+        if let (Some(self_counters), Some(other_counters)) =
+            (self.counters.as_mut(), other.counters.as_ref())
+        {
+            let ceiling = self.counting_ceiling();
+            let mut saturated = false;
+            for (counter, other_counter) in self_counters.iter_mut().zip(other_counters.iter()) {
+                let sum = *counter as u16 + *other_counter as u16;
+                if sum > ceiling as u16 {
+                    *counter = ceiling;
+                    saturated = true;
+                } else {
+                    *counter = sum as u8;
+                }
+            }
+            if saturated {
+                self.counting_saturated = true;
+            }
+        }
+        self.num_items = self.estimated_cardinality();
+    }
+
+    /// Merges `clone` into `self`, where `clone` began life as `BloomFilter::create_copy_from(base)` and
+    /// then had a worker's share of a batch set into it on top of `base`'s existing state. ORs `clone`'s
+    /// bitmap into `self` as `or_bits` does, but for `counters` only folds in the *increase* over `base`
+    /// rather than `clone`'s raw counts: summing `clone`'s counters outright (as `union_with` does for two
+    /// independently populated filters) would double-count every item `base` already held once per sibling
+    /// worker clone, since every clone started from the same `base` counts. Leaves `num_items` to the
+    /// caller, which already knows the exact number of new items the batch added.
+    fn merge_new_items_from(&mut self, base: &BloomFilter, clone: &BloomFilter) {
+        self.or_bits(clone);
+        if let (Some(self_counters), Some(base_counters), Some(clone_counters)) = (
+            self.counters.as_mut(),
+            base.counters.as_ref(),
+            clone.counters.as_ref(),
+        ) {
+            let ceiling = self.counting_ceiling();
+            let mut saturated = false;
+            for ((counter, base_counter), clone_counter) in self_counters
+                .iter_mut()
+                .zip(base_counters.iter())
+                .zip(clone_counters.iter())
+            {
+                let delta = clone_counter.saturating_sub(*base_counter) as u16;
+                let sum = *counter as u16 + delta;
+                if sum > ceiling as u16 {
+                    *counter = ceiling;
+                    saturated = true;
+                } else {
+                    *counter = sum as u8;
+                }
+            }
+            if saturated {
+                self.counting_saturated = true;
+            }
+        }
     }
 }
 
 impl Drop for BloomObject {
     fn drop(&mut self) {
         metrics::BLOOM_OBJECT_TOTAL_MEMORY_BYTES.fetch_sub(
-            self.bloom_object_memory_usage(),
+            self.bloom_object_memory_usage() + self.non_filter_backend_bytes(),
             std::sync::atomic::Ordering::Relaxed,
         );
         metrics::BLOOM_NUM_OBJECTS.fetch_sub(1, Ordering::Relaxed);
@@ -711,6 +2419,7 @@ impl Drop for BloomFilter {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::bloom::compression::BitmapCodec;
     use crate::configs::TIGHTENING_RATIO_DEFAULT;
     use configs;
     use rand::{distributions::Alphanumeric, Rng};
@@ -1075,13 +2784,26 @@ mod tests {
         let capacity = 76000000;
         // With the capacity and fp rate, the memory usage will be roughly 130MB which is greater than the allowed limit.
         assert!(!BloomObject::validate_size_before_create(
-            capacity, 0.001_f64
+            capacity, 0.001_f64, None
         ));
         let result2 =
             BloomObject::new_reserved(0.001_f64, 0.5_f64, capacity, 1, (None, true), true);
         assert_eq!(result2.err(), Some(BloomError::ExceedsMaxBloomSize));
     }
 
+    #[test]
+    fn test_counting_size_estimate_accounts_for_counters() {
+        // A COUNTING filter's pre-creation size estimate must include the counters byte array
+        // (one byte per bitmap bit), not just the bitmap itself, or a reservation that barely fits
+        // under the limit as a plain filter could still blow the real allocation budget once counting
+        // adds eight times the bitmap's size to its footprint.
+        let plain_size = BloomFilter::compute_size(100000, 0.01, None);
+        let counting_size = BloomFilter::compute_size(100000, 0.01, Some(4));
+        assert!(counting_size > plain_size);
+        let bitmap_bytes = bloomfilter::Bloom::<[u8]>::compute_bitmap_size(100000, 0.01);
+        assert_eq!(counting_size - plain_size, bitmap_bytes * 8);
+    }
+
     #[rstest]
     #[case(1000, 0.01, 10000, 2, 15000)]
     #[case(10000, 0.001, 100000, 4, 210000)]
@@ -1104,6 +2826,7 @@ mod tests {
                 .parse()
                 .expect("global config should always be 0.5"),
             expansion,
+            None,
         );
         assert_eq!(resulting_size, returned_size.unwrap());
         // Test that with a -1 validate_scale_to the returned value will be the max capacity
@@ -1115,6 +2838,7 @@ mod tests {
                 .parse()
                 .expect("global config should always be 0.5"),
             expansion,
+            None,
         );
         // Check that 1 more than the max will trigger the error cases
         let failed_returned_size = BloomObject::calculate_max_scaled_capacity(
@@ -1125,6 +2849,7 @@ mod tests {
                 .parse()
                 .expect("global config should always be 0.5"),
             expansion,
+            None,
         );
         if expansion == 1 {
             // FP rate reaches 0 case
@@ -1163,6 +2888,16 @@ mod tests {
         assert_eq!(bf.capacity(), new_bf.capacity());
         // verify item1 exists.
         assert!(new_bf.item_exists(item.as_bytes()));
+        // `encode_object` now always compresses the bincode payload (tagged
+        // `BLOOM_OBJECT_COMPRESSED_VERSION`), so the round trip should still preserve every sub-filter's
+        // bitmap exactly, and the decoded object's in-memory accounting should match the original's -
+        // compression only shrinks the wire format, never the live `BloomFilter`.
+        assert_eq!(vec[0], BLOOM_OBJECT_COMPRESSED_VERSION);
+        assert_eq!(bf.filters.len(), new_bf.filters.len());
+        for (orig, decoded) in bf.filters.iter().zip(new_bf.filters.iter()) {
+            assert_eq!(orig.raw_bloom().as_slice(), decoded.raw_bloom().as_slice());
+        }
+        assert_eq!(bf.memory_usage(), new_bf.memory_usage());
     }
 
     #[test]
@@ -1209,6 +2944,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_bf_decode_rejects_a_length_prefix_claiming_more_than_the_payload_holds() {
+        let mut bf =
+            BloomObject::new_reserved(0.5_f64, 0.5_f64, 1000_i64, 2, (None, true), true).unwrap();
+        let _ = bf.add_item(b"key", true);
+        let mut vec = bf.encode_object().unwrap();
+
+        // `encode_object` wraps the raw bincode body in `compression::compress`'s 5-byte header (codec tag
+        // + original length), after which the bincode tuple lays out `expansion: u32` (4 bytes),
+        // `fp_rate: f64` (8 bytes), `tightening_ratio: f64` (8 bytes), `is_seed_random: bool` (1 byte), then
+        // the `filters: Vec<Box<BloomFilter>>` field's 8-byte little-endian length prefix. Overwriting just
+        // that length prefix with a huge value - while leaving everything before it, including the 5-byte
+        // compression header's own length field, untouched - mimics a corrupted or hostile `RESTORE`
+        // argument. Before `bincode::options().with_limit(..)` was wired in, this is exactly the shape that
+        // let an attacker-sized allocation reach the allocator before the deserializer noticed the input
+        // was too short to back it.
+        let filters_len_prefix_start = 1 + 5 + 4 + 8 + 8 + 1;
+        let huge_len: u64 = u64::MAX / 2;
+        vec.splice(
+            filters_len_prefix_start..filters_len_prefix_start + 8,
+            huge_len.to_le_bytes(),
+        );
+
+        assert_eq!(
+            BloomObject::decode_object(&vec, true).err(),
+            Some(BloomError::DecodeBloomFilterFailed)
+        );
+    }
+
     #[test]
     fn test_bf_decode_when_bytes_is_exceed_limit_should_failed() {
         // arrange: prepare bloom filter
@@ -1240,6 +3004,362 @@ mod tests {
         );
     }
 
+    #[rstest(expansion, case::nonscaling(0), case::scaling(2))]
+    fn test_bf_merge(expansion: u32) {
+        // Use a fixed seed so the two independently created objects are bit-compatible.
+        let mut bf1 = BloomObject::new_reserved(
+            0.01_f64,
+            0.5_f64,
+            1000_i64,
+            expansion,
+            (Some(configs::FIXED_SEED), false),
+            true,
+        )
+        .unwrap();
+        let mut bf2 = BloomObject::new_reserved(
+            0.01_f64,
+            0.5_f64,
+            1000_i64,
+            expansion,
+            (Some(configs::FIXED_SEED), false),
+            true,
+        )
+        .unwrap();
+        let _ = bf1.add_item(b"only_in_one", true);
+        let _ = bf2.add_item(b"only_in_two", true);
+        bf1.merge_from(&bf2).expect("compatible merge should succeed");
+        assert!(bf1.item_exists(b"only_in_one"));
+        assert!(bf1.item_exists(b"only_in_two"));
+        // The merged cardinality is a popcount-based estimate rather than a sum, but with only two items
+        // inserted across two large, low-fp-rate filters it should land close to the true count of 2.
+        assert!((1..=3).contains(&bf1.cardinality()));
+    }
+
+    #[test]
+    fn test_bf_merge_updates_global_item_metric() {
+        // `union_with` re-estimates num_items from popcount, which can move the per-object total in
+        // either direction; the merge must fold that change into the global counter rather than leaving
+        // it reflecting only the pre-merge item counts.
+        let mut bf1 = BloomObject::new_reserved(
+            0.01_f64,
+            0.5_f64,
+            1000_i64,
+            2,
+            (Some(configs::FIXED_SEED), false),
+            true,
+        )
+        .unwrap();
+        let bf2 = BloomObject::new_reserved(
+            0.01_f64,
+            0.5_f64,
+            1000_i64,
+            2,
+            (Some(configs::FIXED_SEED), false),
+            true,
+        )
+        .unwrap();
+        let before = metrics::BLOOM_NUM_ITEMS_ACROSS_OBJECTS.load(Ordering::Relaxed);
+        bf1.merge_from(&bf2).expect("compatible merge should succeed");
+        let items_after_merge: i64 = bf1.filters().iter().map(|f| f.num_items()).sum();
+        let after = metrics::BLOOM_NUM_ITEMS_ACROSS_OBJECTS.load(Ordering::Relaxed);
+        assert_eq!(after as i64 - before as i64, items_after_merge);
+    }
+
+    #[test]
+    fn test_bf_merge_rejects_incompatible_filters() {
+        let mut bf1 =
+            BloomObject::new_reserved(0.01_f64, 0.5_f64, 1000_i64, 2, (None, true), true).unwrap();
+        let bf2 =
+            BloomObject::new_reserved(0.01_f64, 0.5_f64, 2000_i64, 2, (None, true), true).unwrap();
+        assert_eq!(
+            bf1.merge_from(&bf2).err(),
+            Some(BloomError::MergeFiltersIncompatible)
+        );
+    }
+
+    #[test]
+    fn test_bf_merge_rejects_mismatched_bitmap_shape() {
+        // Same capacity/fp_rate/seed on both sides, but bf2's sole sub-filter is forced to a different
+        // bitmap length than the one that capacity/fp_rate would normally produce - e.g. what
+        // `bloom-optimize-for-memory` rounding could legitimately leave two otherwise-identical filters
+        // with. `merge_from` must reject this rather than silently truncating via `or_bits`'s zip.
+        let seed = [7u8; 32];
+        let mut bf1 =
+            BloomObject::new_reserved(0.01_f64, 0.5_f64, 1000_i64, 2, (Some(seed), false), true)
+                .unwrap();
+        let mut bf2 =
+            BloomObject::new_reserved(0.01_f64, 0.5_f64, 1000_i64, 2, (Some(seed), false), true)
+                .unwrap();
+        let mismatched_bytes = Bloom::<[u8]>::compute_bitmap_size(1000, 0.01) * 2;
+        let mismatched_bloom = Bloom::new_with_seed(mismatched_bytes, 1000, &seed)
+            .expect("bitmap of a different size should still construct");
+        bf2.filters_mut()[0].bloom = Box::new(mismatched_bloom);
+        assert_eq!(bf1.filters().len(), bf2.filters().len());
+        assert_eq!(
+            bf1.merge_from(&bf2).err(),
+            Some(BloomError::MergeFiltersIncompatible)
+        );
+    }
+
+    #[test]
+    fn test_check_items_parallel_matches_serial() {
+        let mut bf =
+            BloomObject::new_reserved(0.01_f64, 0.5_f64, 1000_i64, 2, (None, true), true).unwrap();
+        let present: Vec<Vec<u8>> = (0..200).map(|i| format!("item-{i}").into_bytes()).collect();
+        for item in &present {
+            let _ = bf.add_item(item, true);
+        }
+        let absent: Vec<Vec<u8>> = (0..200).map(|i| format!("absent-{i}").into_bytes()).collect();
+        let items: Vec<&[u8]> = present
+            .iter()
+            .chain(absent.iter())
+            .map(|i| i.as_slice())
+            .collect();
+        let parallel_results = bf.check_items_parallel(&items, 4);
+        let serial_results: Vec<bool> = items.iter().map(|item| bf.item_exists(item)).collect();
+        assert_eq!(parallel_results, serial_results);
+        assert!(parallel_results[..200].iter().all(|&found| found));
+    }
+
+    #[test]
+    fn test_add_items_matches_serial_add_item() {
+        // Small enough capacity that the batch below forces at least one scale-out, and includes an
+        // in-batch duplicate plus an already-present item, to exercise the same corner cases a loop of
+        // `add_item` calls would see.
+        let mut bf =
+            BloomObject::new_reserved(0.01_f64, 0.5_f64, 10_i64, 2, (None, true), true).unwrap();
+        assert_eq!(bf.add_item(b"already-present", true).unwrap(), 1);
+
+        let mut batch: Vec<Vec<u8>> = (0..20).map(|i| format!("item-{i}").into_bytes()).collect();
+        batch.push(b"already-present".to_vec());
+        batch.push(b"item-5".to_vec());
+        let items: Vec<&[u8]> = batch.iter().map(|i| i.as_slice()).collect();
+
+        let results = bf.add_items(&items, true).unwrap();
+        assert_eq!(results.len(), items.len());
+        for (i, item) in items.iter().enumerate().take(20) {
+            assert_eq!(results[i], 1, "item {item:?} should be newly added");
+        }
+        // The already-present item and the in-batch duplicate of "item-5" both report 0.
+        assert_eq!(results[20], 0);
+        assert_eq!(results[21], 0);
+        for item in &items {
+            assert!(bf.item_exists(item));
+        }
+        assert!(bf.num_filters() > 1, "batch should have forced a scale-out");
+    }
+
+    #[test]
+    fn test_add_items_bulk_matches_serial_add_item() {
+        // A batch of over twice `bloom-bulk-parallel-threshold` against a small starting capacity, so it
+        // forces a scale-out partway through and the share landing in the new sub-filter is itself above
+        // the threshold, exercising the clone-and-union worker path.
+        let mut bf =
+            BloomObject::new_reserved(0.01_f64, 0.5_f64, 5000_i64, 4, (None, true), true).unwrap();
+        assert_eq!(bf.add_item(b"already-present", true).unwrap(), 1);
+
+        let threshold = configs::BLOOM_BULK_PARALLEL_THRESHOLD.load(Ordering::Relaxed);
+        let mut batch: Vec<Vec<u8>> = (0..(2 * threshold + 500))
+            .map(|i| format!("item-{i}").into_bytes())
+            .collect();
+        batch.push(b"already-present".to_vec());
+        batch.push(b"item-5".to_vec());
+        let items: Vec<&[u8]> = batch.iter().map(|i| i.as_slice()).collect();
+
+        let results = bf.add_items_bulk(&items, true).unwrap();
+        assert_eq!(results.len(), items.len());
+        for (i, item) in items.iter().enumerate().take(2 * threshold as usize + 500) {
+            assert_eq!(results[i], 1, "item {item:?} should be newly added");
+        }
+        assert_eq!(results[2 * threshold as usize + 500], 0);
+        assert_eq!(results[2 * threshold as usize + 501], 0);
+        for item in &items {
+            assert!(bf.item_exists(item));
+        }
+        assert!(bf.num_filters() > 1, "batch should have forced a scale-out");
+    }
+
+    #[test]
+    fn test_add_items_bulk_parallelizes_counting_mode_without_losing_counts() {
+        // With a sub-filter's share of the batch above `bloom-bulk-parallel-threshold` (the batch is over
+        // twice the threshold and scales out past a 5000-item first filter), a COUNTING object must take
+        // the same clone-and-merge worker path as a plain object instead of falling back to serial `add_items`, and still come out
+        // with correct per-slot counters - verified by deleting one item afterward and confirming it alone
+        // disappears, with no risk of `CountingFilterSaturated` rejecting the delete.
+        let mut bf = BloomObject::new_reserved_with_counting(
+            0.01_f64,
+            0.5_f64,
+            5000_i64,
+            4,
+            (None, true),
+            true,
+            Some(4),
+        )
+        .unwrap();
+        assert!(bf.is_counting());
+        let _ = bf.add_item(b"already-present", true).unwrap();
+
+        let threshold = configs::BLOOM_BULK_PARALLEL_THRESHOLD.load(Ordering::Relaxed);
+        let mut batch: Vec<Vec<u8>> = (0..(2 * threshold + 500))
+            .map(|i| format!("item-{i}").into_bytes())
+            .collect();
+        batch.push(b"already-present".to_vec());
+        let items: Vec<&[u8]> = batch.iter().map(|i| i.as_slice()).collect();
+
+        let results = bf.add_items_bulk(&items, true).unwrap();
+        assert_eq!(results.len(), items.len());
+        for (i, item) in items.iter().enumerate().take(2 * threshold as usize + 500) {
+            assert_eq!(results[i], 1, "item {item:?} should be newly added");
+        }
+        assert_eq!(results[2 * threshold as usize + 500], 0);
+        for item in &items {
+            assert!(bf.item_exists(item));
+        }
+        assert!(bf.num_filters() > 1, "batch should have forced a scale-out");
+        assert!(!bf.any_counter_saturated());
+
+        assert_eq!(bf.delete_item(b"item-5").unwrap(), 1);
+        assert!(!bf.item_exists(b"item-5"));
+        assert!(bf.item_exists(b"item-6"));
+        assert!(bf.item_exists(b"already-present"));
+    }
+
+    #[test]
+    fn test_bf_filter_memory_breakdown() {
+        let mut bf =
+            BloomObject::new_reserved(0.01_f64, 0.5_f64, 1000_i64, 2, (None, true), true).unwrap();
+        for i in 0..10 {
+            let _ = bf.add_item(format!("item-{i}").as_bytes(), true);
+        }
+        let breakdown = bf.filter_memory_breakdown();
+        assert_eq!(breakdown.len(), bf.num_filters());
+        for (bytes, bits_set, bits_total) in breakdown {
+            assert!(bytes > 0);
+            assert!(bits_set <= bits_total);
+        }
+        assert!(bf.overhead_bytes() > 0);
+    }
+
+    #[rstest(expansion, case::nonscaling(0), case::scaling(2))]
+    fn test_bf_scandump_loadchunk_roundtrip(expansion: u32) {
+        let mut bf = BloomObject::new_reserved(
+            0.01_f64,
+            0.5_f64,
+            10_i64,
+            expansion,
+            (Some(configs::FIXED_SEED), false),
+            true,
+        )
+        .unwrap();
+        for i in 0..50 {
+            let _ = bf.add_item(format!("item-{i}").as_bytes(), true);
+        }
+
+        let num_chunks = bf.num_scandump_chunks();
+        assert!(num_chunks >= 2, "expected a header chunk plus at least one sub-filter chunk");
+        let header_bytes = bf.encode_scandump_chunk(0).unwrap();
+        let (
+            expansion_out,
+            fp_rate_out,
+            tightening_ratio_out,
+            is_seed_random_out,
+            num_filters,
+            counting_bits_out,
+        ) = BloomObject::decode_scandump_header(&header_bytes).unwrap();
+        assert_eq!(expansion_out, bf.expansion());
+        assert_eq!(fp_rate_out, bf.fp_rate());
+        assert_eq!(tightening_ratio_out, bf.tightening_ratio());
+        assert_eq!(is_seed_random_out, bf.is_seed_random());
+        assert_eq!(num_filters, num_chunks - 1);
+
+        let mut filters = Vec::with_capacity(num_filters);
+        for chunk_idx in 1..num_chunks {
+            let chunk_bytes = bf.encode_scandump_chunk(chunk_idx).unwrap();
+            filters.push(BloomObject::decode_scandump_filter_chunk(&chunk_bytes).unwrap());
+        }
+
+        let rebuilt = BloomObject::from_existing(
+            expansion_out,
+            fp_rate_out,
+            tightening_ratio_out,
+            is_seed_random_out,
+            filters,
+            counting_bits_out,
+            true,
+        )
+        .unwrap();
+        // The nonscaling case holds only its first 10 items (the rest are rejected once it is full), so
+        // compare against the original object rather than expecting all 50.
+        for i in 0..50 {
+            let item = format!("item-{i}");
+            assert_eq!(rebuilt.item_exists(item.as_bytes()), bf.item_exists(item.as_bytes()));
+        }
+        assert_eq!(rebuilt.cardinality(), bf.cardinality());
+    }
+
+    #[rstest(
+        codec,
+        case::none(BitmapCodec::None),
+        case::snappy(BitmapCodec::Snappy),
+        case::lz4(BitmapCodec::Lz4)
+    )]
+    fn test_bitmap_compression_roundtrip(codec: BitmapCodec) {
+        // A highly repetitive bitmap, the common case for a mostly-empty filter, should compress.
+        let bytes = vec![0u8; 4096];
+        let compressed = compression::compress(&bytes, codec);
+        if codec != BitmapCodec::None {
+            assert!(compressed.len() < bytes.len());
+        }
+        let decompressed = compression::decompress(&compressed).unwrap();
+        assert_eq!(decompressed, bytes);
+    }
+
+    #[test]
+    fn test_bitmap_compression_falls_back_when_not_smaller() {
+        // Small/incompressible input: compression must not be allowed to make the chunk bigger.
+        let bytes: Vec<u8> = (0..8).collect();
+        let compressed = compression::compress(&bytes, BitmapCodec::Lz4);
+        let decompressed = compression::decompress(&compressed).unwrap();
+        assert_eq!(decompressed, bytes);
+    }
+
+    #[test]
+    fn test_usable_bitmap_bytes_never_shrinks_and_handles_zero() {
+        assert_eq!(usable_bitmap_bytes(0), 0);
+        let requested = 128;
+        assert!(usable_bitmap_bytes(requested) >= requested);
+    }
+
+    #[test]
+    fn test_size_for_capacity_is_unchanged_when_disabled() {
+        // `bloom-optimize-for-memory` defaults to off, so sizing should be a pure pass-through of the
+        // capacity/fp_rate implied bit count with no accumulated bias.
+        let expected_bits = (bloomfilter::Bloom::<[u8]>::compute_bitmap_size(1000, 0.01) as u64) * 8;
+        let (bits, bias) = BloomFilter::size_for_capacity(1000, 0.01, 0, false);
+        assert_eq!(bits, expected_bits);
+        assert_eq!(bias, 0);
+    }
+
+    #[test]
+    fn test_pow2_sizing_rounds_bitmap_up_and_skips_bias() {
+        // A capacity/fp_rate combination whose implied bit count isn't already a power of two.
+        let requested_bits =
+            (bloomfilter::Bloom::<[u8]>::compute_bitmap_size(1000, 0.01) as u64) * 8;
+        assert!(
+            !requested_bits.is_power_of_two(),
+            "test fixture must exercise an actual rounding-up case"
+        );
+        let (bits, bias) = BloomFilter::size_for_capacity(1000, 0.01, 0, true);
+        assert!(bits.is_power_of_two());
+        assert!(bits >= requested_bits);
+        assert_eq!(bias, 0);
+
+        // Surfaced through the live filter's own accounting, not just the sizing helper in isolation.
+        let filter = BloomFilter::with_random_seed_counting_pow2(0.01, 1000, None).unwrap();
+        assert!(filter.bits_total().is_power_of_two());
+        assert_eq!(filter.bits_total(), bits);
+    }
+
     #[test]
     fn test_vec_capacity_matches_size_calculations() {
         // This unit test is designed to make sure out calculations with capcity will always match the correct vec capacity
@@ -1254,4 +3374,140 @@ mod tests {
             test_v.push(i);
         }
     }
+
+    #[test]
+    fn test_scale_out_keeps_filters_capacity_exact() {
+        // Unlike a plain `Vec::push` loop - see `test_vec_capacity_matches_size_calculations` - `scale_out`
+        // must not let `self.filters` grow its capacity ahead of its length, since
+        // `bloom_object_memory_usage` bills the object for `self.filters.capacity()`.
+        let mut bf =
+            BloomObject::new_reserved(0.01_f64, 0.5_f64, 10_i64, 2, (None, true), true).unwrap();
+        assert_eq!(bf.filters.capacity(), bf.filters.len());
+        for i in 0..40 {
+            let _ = bf.add_item(format!("item-{i}").as_bytes(), true);
+            assert_eq!(
+                bf.filters.capacity(),
+                bf.filters.len(),
+                "filters Vec should never reserve ahead of its length"
+            );
+        }
+        assert!(bf.filters.len() > 1, "test should have actually scaled out");
+    }
+
+    #[test]
+    fn test_counting_filter_delete() {
+        // A COUNTING filter should support deleting an item without affecting unrelated items.
+        let mut bf = BloomObject::new_reserved_with_counting(
+            0.01_f64,
+            0.5_f64,
+            1000_i64,
+            2,
+            (None, true),
+            true,
+            Some(4),
+        )
+        .unwrap();
+        assert!(bf.is_counting());
+        let _ = bf.add_item(b"item1", true).unwrap();
+        let _ = bf.add_item(b"item2", true).unwrap();
+        assert!(bf.item_exists(b"item1"));
+        assert!(bf.item_exists(b"item2"));
+
+        assert_eq!(bf.delete_item(b"item1").unwrap(), 1);
+        assert!(!bf.item_exists(b"item1"));
+        assert!(bf.item_exists(b"item2"));
+
+        // Deleting an item that is not present is a no-op that reports 0.
+        assert_eq!(bf.delete_item(b"item1").unwrap(), 0);
+    }
+
+    #[test]
+    fn test_hash_indices_unbiased_for_non_power_of_two_m() {
+        // Capacity/fp_rate combinations routinely land on a bitmap length that isn't a power of two;
+        // `hash_indices` must still only ever return indices below that exact `m`, never folding an
+        // out-of-range candidate back down with a biased `% m`.
+        let bf =
+            BloomFilter::with_fixed_seed_counting(0.01_f64, 777_i64, &[7u8; 32], Some(4)).unwrap();
+        let m = bf.raw_bloom().len() as usize;
+        for i in 0..2000u32 {
+            let item = i.to_le_bytes();
+            for idx in bf.hash_indices(&item) {
+                assert!(idx < m, "index {idx} out of range for m={m}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_non_counting_filter_rejects_delete() {
+        // A plain (non-counting) filter has no counters to decrement, so delete must fail outright.
+        let mut bf =
+            BloomObject::new_reserved(0.01_f64, 0.5_f64, 1000_i64, 2, (None, true), true).unwrap();
+        assert!(!bf.is_counting());
+        let _ = bf.add_item(b"item1", true).unwrap();
+        assert_eq!(
+            bf.delete_item(b"item1").err(),
+            Some(BloomError::NotACountingFilter)
+        );
+    }
+
+    #[test]
+    fn test_counting_filter_saturation_blocks_delete() {
+        // Once any counter saturates, deletes can no longer be trusted to leave the filter in a
+        // consistent state, so BF.DEL must refuse rather than silently corrupt membership.
+        let mut bf = BloomObject::new_reserved_with_counting(
+            0.01_f64,
+            0.5_f64,
+            10_i64,
+            2,
+            (None, true),
+            true,
+            Some(1),
+        )
+        .unwrap();
+        for i in 0..20 {
+            let _ = bf.add_item(format!("item-{i}").as_bytes(), true);
+        }
+        assert!(bf.any_counter_saturated());
+        assert_eq!(
+            bf.delete_item(b"item-0").err(),
+            Some(BloomError::CountingFilterSaturated)
+        );
+    }
+
+    #[test]
+    fn test_sbbf_object_membership_and_readonly() {
+        let mut bf = BloomObject::new_reserved_sbbf(1000, 0.01, true).unwrap();
+        assert!(bf.is_sbbf());
+        assert_eq!(bf.capacity(), 1000);
+        assert_eq!(
+            bf.add_item(b"item1", true).err(),
+            Some(BloomError::SbbfReadOnly)
+        );
+        assert!(!bf.item_exists(b"item1"));
+    }
+
+    #[test]
+    fn test_sbbf_object_encode_decode_roundtrip() {
+        let mut filter = crate::bloom::sbbf::SplitBlockFilter::new_with_ndv_fpp(100, 0.01).unwrap();
+        for i in 0..100 {
+            filter.insert(format!("item-{i}").as_bytes());
+        }
+        let bf = BloomObject {
+            expansion: 0,
+            fp_rate: 0.01,
+            tightening_ratio: 1.0,
+            is_seed_random: true,
+            filters: Vec::new(),
+            counting_bits: None,
+            sbbf: Some(Box::new(filter)),
+            murmur128: None,
+            ribbon: None,
+        };
+        let bytes = bf.encode_object().unwrap();
+        let restored = BloomObject::decode_object(&bytes, true).unwrap();
+        assert!(restored.is_sbbf());
+        for i in 0..100 {
+            assert!(restored.item_exists(format!("item-{i}").as_bytes()));
+        }
+    }
 }
@@ -1,6 +1,7 @@
+use crate::bloom::compression::BitmapCodec;
 use crate::bloom::utils;
 use lazy_static::lazy_static;
-use std::sync::atomic::{AtomicBool, AtomicI64};
+use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
 use std::sync::Mutex;
 use valkey_module::{
     configuration::ConfigurationContext, ConfigurationValue, ValkeyError, ValkeyGILGuard,
@@ -26,10 +27,43 @@ pub const TIGHTENING_RATIO_DEFAULT: &str = "0.5";
 pub const BLOOM_TIGHTENING_RATIO_MIN: f64 = 0.0;
 pub const BLOOM_TIGHTENING_RATIO_MAX: f64 = 1.0;
 
+// Threshold (in number of items) above which BF.MADD/BF.INSERT hash and set bits for a bulk payload
+// across a worker thread pool instead of serially on the main thread. See `bloom::utils::BloomObject::add_items_parallel`.
+pub const BLOOM_BULK_PARALLEL_THRESHOLD_DEFAULT: i64 = 10_000;
+pub const BLOOM_BULK_PARALLEL_THRESHOLD_MIN: i64 = 1;
+pub const BLOOM_BULK_PARALLEL_THRESHOLD_MAX: i64 = i64::MAX;
+
 pub const BLOOM_USE_RANDOM_SEED_DEFAULT: bool = true;
 
+// Codec used to compress each sub-filter's serialized bitmap for BF.SCANDUMP/BF.LOADCHUNK and on-disk
+// storage. One of "none" / "snappy" / "lz4"; see `bloom::compression`.
+pub const BLOOM_BITMAP_COMPRESSION_DEFAULT: &str = "none";
+
+// Construction algorithm for new filters: "bloom" builds the usual sip-hash bitmap; "ribbon" builds a
+// banded, statically-solved Ribbon filter (see `bloom::ribbon`) that seals once it reaches capacity,
+// trading ~30% less memory for giving up the ability to scale out. See `BloomObject::new_reserved_ribbon`.
+pub const BLOOM_FILTER_ALGORITHM_DEFAULT: &str = "bloom";
+
+// Bounds for the `COUNTING [bits]` option on BF.RESERVE/BF.INSERT. Not a module config since it is
+// chosen per-filter at creation time, not globally.
+pub const BLOOM_COUNTING_BITS_DEFAULT: u8 = 4;
+pub const BLOOM_COUNTING_BITS_MIN: u8 = 1;
+pub const BLOOM_COUNTING_BITS_MAX: u8 = 8;
+
 pub const BLOOM_DEFRAG_DEAFULT: bool = true;
 
+// Minimum ratio (as a percentage) of an allocation's logical size to its `malloc_usable_size` below which
+// `Defrag::should_realloc` considers it worth relocating. Mirrors the semantics of Valkey's own
+// `active-defrag-threshold-lower`. See `wrapper::defrag::Defrag::should_realloc`.
+pub const BLOOM_DEFRAG_UTIL_THRESHOLD_DEFAULT: i64 = 10;
+pub const BLOOM_DEFRAG_UTIL_THRESHOLD_MIN: i64 = 1;
+pub const BLOOM_DEFRAG_UTIL_THRESHOLD_MAX: i64 = 100;
+
+// When enabled, a new sub-filter's bitmap is sized up to the allocator's actual usable size (probed via
+// `malloc_usable_size`) rather than the exact byte count `capacity`/fp_rate imply, reclaiming allocator
+// rounding slack that would otherwise sit unused. See `bloom::utils::BloomFilter::size_for_capacity`.
+pub const BLOOM_OPTIMIZE_FOR_MEMORY_DEFAULT: bool = false;
+
 // Max Memory usage allowed overall within a bloom object (128MB).
 // Beyond this threshold, a bloom object is classified as large.
 // Write operations that result in bloom object allocation larger than this size will be rejected.
@@ -37,13 +71,28 @@ pub const BLOOM_MEMORY_LIMIT_PER_OBJECT_DEFAULT: i64 = 128 * 1024 * 1024;
 pub const BLOOM_MEMORY_LIMIT_PER_OBJECT_MIN: i64 = 0;
 pub const BLOOM_MEMORY_LIMIT_PER_OBJECT_MAX: i64 = i64::MAX;
 
+// Module-wide cap (in bytes) on the combined memory footprint of every bloom object taken together,
+// checked in addition to the per-object `BLOOM_MEMORY_LIMIT_PER_OBJECT` cap. 0 disables the check,
+// mirroring `maxmemory 0`. See `bloom::utils::BloomObject::validate_global_memory_budget`.
+pub const BLOOM_TOTAL_MEMORY_LIMIT_DEFAULT: i64 = 0;
+pub const BLOOM_TOTAL_MEMORY_LIMIT_MIN: i64 = 0;
+pub const BLOOM_TOTAL_MEMORY_LIMIT_MAX: i64 = i64::MAX;
+
 lazy_static! {
     pub static ref BLOOM_CAPACITY: AtomicI64 = AtomicI64::new(BLOOM_CAPACITY_DEFAULT);
     pub static ref BLOOM_EXPANSION: AtomicI64 = AtomicI64::new(BLOOM_EXPANSION_DEFAULT);
+    pub static ref BLOOM_BULK_PARALLEL_THRESHOLD: AtomicI64 =
+        AtomicI64::new(BLOOM_BULK_PARALLEL_THRESHOLD_DEFAULT);
     pub static ref BLOOM_MEMORY_LIMIT_PER_OBJECT: AtomicI64 =
         AtomicI64::new(BLOOM_MEMORY_LIMIT_PER_OBJECT_DEFAULT);
+    pub static ref BLOOM_TOTAL_MEMORY_LIMIT: AtomicI64 =
+        AtomicI64::new(BLOOM_TOTAL_MEMORY_LIMIT_DEFAULT);
     pub static ref BLOOM_USE_RANDOM_SEED: AtomicBool = AtomicBool::default();
     pub static ref BLOOM_DEFRAG: AtomicBool = AtomicBool::new(BLOOM_DEFRAG_DEAFULT);
+    pub static ref BLOOM_DEFRAG_UTIL_THRESHOLD: AtomicI64 =
+        AtomicI64::new(BLOOM_DEFRAG_UTIL_THRESHOLD_DEFAULT);
+    pub static ref BLOOM_OPTIMIZE_FOR_MEMORY: AtomicBool =
+        AtomicBool::new(BLOOM_OPTIMIZE_FOR_MEMORY_DEFAULT);
     pub static ref BLOOM_FP_RATE_F64: Mutex<f64> = Mutex::new(
         BLOOM_FP_RATE_DEFAULT
             .parse::<f64>()
@@ -58,6 +107,37 @@ lazy_static! {
     );
     pub static ref BLOOM_TIGHTENING_RATIO: ValkeyGILGuard<ValkeyString> =
         ValkeyGILGuard::new(ValkeyString::create(None, TIGHTENING_RATIO_DEFAULT));
+    pub static ref BLOOM_BITMAP_COMPRESSION: ValkeyGILGuard<ValkeyString> =
+        ValkeyGILGuard::new(ValkeyString::create(None, BLOOM_BITMAP_COMPRESSION_DEFAULT));
+    pub static ref BLOOM_BITMAP_COMPRESSION_CODEC: Mutex<BitmapCodec> = Mutex::new(
+        BitmapCodec::from_config_str(BLOOM_BITMAP_COMPRESSION_DEFAULT)
+            .expect("default bitmap compression codec must be valid")
+    );
+    pub static ref BLOOM_FILTER_ALGORITHM: ValkeyGILGuard<ValkeyString> =
+        ValkeyGILGuard::new(ValkeyString::create(None, BLOOM_FILTER_ALGORITHM_DEFAULT));
+    pub static ref BLOOM_FILTER_ALGORITHM_IS_RIBBON: AtomicBool = AtomicBool::new(false);
+}
+
+/// Returns whether new sub-filter bitmaps should be sized up to the allocator's actual usable size
+/// instead of the exact byte count implied by capacity/fp_rate. See `BLOOM_OPTIMIZE_FOR_MEMORY`.
+pub fn optimize_for_memory() -> bool {
+    BLOOM_OPTIMIZE_FOR_MEMORY.load(Ordering::Relaxed)
+}
+
+/// Returns whether new non-scaling filters should be built with the Ribbon backend rather than the
+/// default sip-hash bitmap. Reads the cached atomic rather than re-parsing `BLOOM_FILTER_ALGORITHM` so
+/// callers don't need GIL access, mirroring `bitmap_compression_codec`.
+pub fn filter_algorithm_is_ribbon() -> bool {
+    BLOOM_FILTER_ALGORITHM_IS_RIBBON.load(Ordering::Relaxed)
+}
+
+/// Returns the currently configured bitmap compression codec for new `BF.SCANDUMP` chunks. Reads the
+/// cached `Mutex<BitmapCodec>` rather than re-parsing `BLOOM_BITMAP_COMPRESSION` so callers don't need
+/// GIL access, mirroring `BLOOM_FP_RATE_F64`/`BLOOM_TIGHTENING_F64`.
+pub fn bitmap_compression_codec() -> BitmapCodec {
+    *BLOOM_BITMAP_COMPRESSION_CODEC
+        .lock()
+        .expect("We expect the bitmap compression codec static to exist.")
 }
 
 /// Constants
@@ -70,7 +150,7 @@ pub const FIXED_SEED: [u8; 32] = [
     152, 136, 135, 48, 127, 151, 205, 40, 7, 51, 131,
 ];
 
-/// This is a config set handler for the False Positive Rate and Tightening Ratio configs.
+/// This is a config set handler for the False Positive Rate, Tightening Ratio and Bitmap Compression configs.
 pub fn on_string_config_set(
     config_ctx: &ConfigurationContext,
     name: &str,
@@ -78,6 +158,24 @@ pub fn on_string_config_set(
 ) -> Result<(), ValkeyError> {
     let v = val.get(config_ctx);
     let value_str = v.to_string_lossy();
+    if name == "bloom-bitmap-compression" {
+        let codec = BitmapCodec::from_config_str(&value_str)
+            .map_err(|_| ValkeyError::Str(utils::BAD_BITMAP_COMPRESSION))?;
+        let mut current = BLOOM_BITMAP_COMPRESSION_CODEC
+            .lock()
+            .expect("We expect the bitmap compression codec static to exist.");
+        *current = codec;
+        return Ok(());
+    }
+    if name == "bloom-filter-algorithm" {
+        let is_ribbon = match value_str.to_lowercase().as_str() {
+            "bloom" => false,
+            "ribbon" => true,
+            _ => return Err(ValkeyError::Str(utils::BAD_FILTER_ALGORITHM)),
+        };
+        BLOOM_FILTER_ALGORITHM_IS_RIBBON.store(is_ribbon, Ordering::Relaxed);
+        return Ok(());
+    }
     let value = match value_str.parse::<f64>() {
         Ok(v) => v,
         Err(_) => {
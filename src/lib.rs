@@ -1,19 +1,27 @@
 use metrics::bloom_info_handler;
 use valkey_module::{
-    configuration::ConfigurationFlags, valkey_module, Context, InfoContext, Status, ValkeyGILGuard,
-    ValkeyResult, ValkeyString,
+    configuration::ConfigurationFlags, raw, valkey_module, Context, InfoContext, Status,
+    ValkeyGILGuard, ValkeyResult, ValkeyString,
 };
 pub mod bloom;
 pub mod configs;
 pub mod metrics;
 pub mod wrapper;
 use crate::bloom::command_handler;
+use crate::bloom::data_type::BLOOM_CASCADE_TYPE;
 use crate::bloom::data_type::BLOOM_FILTER_TYPE;
+use crate::wrapper::bloom_callback::bloom_defrag_global;
 use valkey_module_macros::info_command_handler;
 
 pub const MODULE_NAME: &str = "bf";
 
-fn initialize(_ctx: &Context, _args: &[ValkeyString]) -> Status {
+fn initialize(ctx: &Context, _args: &[ValkeyString]) -> Status {
+    // Register the global defrag callback for state not tied to any single key (the
+    // `DEFRAG_BLOOM_FILTER` placeholder). The per-key callback (`bloom_defrag`) is registered
+    // separately via `BLOOM_FILTER_TYPE`'s `defrag_func`.
+    unsafe {
+        raw::RedisModule_RegisterDefragFunc.unwrap()(ctx.ctx, Some(bloom_defrag_global));
+    }
     Status::Ok
 }
 
@@ -46,18 +54,18 @@ fn bloom_card_command(ctx: &Context, args: Vec<ValkeyString>) -> ValkeyResult {
     command_handler::bloom_filter_card(ctx, &args)
 }
 
-/// Command handler for BF.RESERVE <key> <false_positive_rate> <capacity> [EXPANSION <expansion>] | [NONSCALING]
+/// Command handler for BF.RESERVE <key> <false_positive_rate> <capacity> [EXPANSION <expansion>] | [NONSCALING] [COUNTING [bits]] | [SBBF] | [HASH <default|murmur128>]
 fn bloom_reserve_command(ctx: &Context, args: Vec<ValkeyString>) -> ValkeyResult {
     command_handler::bloom_filter_reserve(ctx, &args)
 }
 
-/// Command handler for BF.INFO <key> [CAPACITY | SIZE | FILTERS | ITEMS | EXPANSION]
+/// Command handler for BF.INFO <key> [CAPACITY | SIZE | FILTERS | ITEMS | EXPANSION | MEMORY | FILLRATIO | ERROR | CURRENTERROR | FILTERSDETAIL]
 fn bloom_info_command(ctx: &Context, args: Vec<ValkeyString>) -> ValkeyResult {
     command_handler::bloom_filter_info(ctx, &args)
 }
 
 /// Command handler for:
-/// BF.INSERT <key> [ERROR <fp_error>] [CAPACITY <capacity>] [EXPANSION <expansion>] [NOCREATE] [NONSCALING] ITEMS <item> [<item> ...]
+/// BF.INSERT <key> [ERROR <fp_error>] [CAPACITY <capacity>] [EXPANSION <expansion>] [NOCREATE] [NONSCALING] [COUNTING [bits]] [JOBS <count>] ITEMS <item> [<item> ...]
 fn bloom_insert_command(ctx: &Context, args: Vec<ValkeyString>) -> ValkeyResult {
     command_handler::bloom_filter_insert(ctx, &args)
 }
@@ -68,6 +76,66 @@ fn bloom_load_command(ctx: &Context, args: Vec<ValkeyString>) -> ValkeyResult {
     command_handler::bloom_filter_load(ctx, &args)
 }
 
+/// Command handler for:
+/// BF.MERGE <dest> <src1> [<src2> ...]
+fn bloom_merge_command(ctx: &Context, args: Vec<ValkeyString>) -> ValkeyResult {
+    command_handler::bloom_filter_merge(ctx, &args)
+}
+
+/// Command handler for:
+/// BF.SCANDUMP <key> <iterator>
+fn bloom_scandump_command(ctx: &Context, args: Vec<ValkeyString>) -> ValkeyResult {
+    command_handler::bloom_filter_scandump(ctx, &args)
+}
+
+/// Command handler for:
+/// BF.LOADCHUNK <key> <iterator> <data>
+fn bloom_loadchunk_command(ctx: &Context, args: Vec<ValkeyString>) -> ValkeyResult {
+    command_handler::bloom_filter_loadchunk(ctx, &args)
+}
+
+/// Command handler for:
+/// BF.EXPORT <key>
+fn bloom_export_command(ctx: &Context, args: Vec<ValkeyString>) -> ValkeyResult {
+    command_handler::bloom_filter_export(ctx, &args)
+}
+
+/// Command handler for:
+/// BF.IMPORT <key> <ndv> <fp_rate> <data>
+fn bloom_import_command(ctx: &Context, args: Vec<ValkeyString>) -> ValkeyResult {
+    command_handler::bloom_filter_import(ctx, &args)
+}
+
+/// Command handler for:
+/// BF.DEL <key> <item> [<item> ...]
+fn bloom_del_command(ctx: &Context, args: Vec<ValkeyString>) -> ValkeyResult {
+    command_handler::bloom_filter_delete(ctx, &args)
+}
+
+/// Command handler for:
+/// BF.CASCADE.BUILD <key> <fp_rate> INCLUDE <item> [<item> ...] EXCLUDE <item> [<item> ...] [SEEDS <seed> [<seed> ...]]
+fn bloom_cascade_build_command(ctx: &Context, args: Vec<ValkeyString>) -> ValkeyResult {
+    command_handler::bloom_cascade_build(ctx, &args)
+}
+
+/// Command handler for:
+/// BF.CASCADE.LOAD <key> <data>
+fn bloom_cascade_load_command(ctx: &Context, args: Vec<ValkeyString>) -> ValkeyResult {
+    command_handler::bloom_cascade_load(ctx, &args)
+}
+
+/// Command handler for:
+/// BF.CASCADE.QUERY <key> <item> [<item> ...]
+fn bloom_cascade_query_command(ctx: &Context, args: Vec<ValkeyString>) -> ValkeyResult {
+    command_handler::bloom_cascade_query(ctx, &args)
+}
+
+/// Command handler for:
+/// BF.CASCADE.INFO <key>
+fn bloom_cascade_info_command(ctx: &Context, args: Vec<ValkeyString>) -> ValkeyResult {
+    command_handler::bloom_cascade_info(ctx, &args)
+}
+
 ///
 /// Module Info
 ///
@@ -84,6 +152,7 @@ valkey_module! {
     allocator: (valkey_module::alloc::ValkeyAlloc, valkey_module::alloc::ValkeyAlloc),
     data_types: [
         BLOOM_FILTER_TYPE,
+        BLOOM_CASCADE_TYPE,
     ],
     init: initialize,
     deinit: deinitialize,
@@ -99,21 +168,37 @@ valkey_module! {
         ["BF.RESERVE", bloom_reserve_command, "write fast deny-oom", 1, 1, 1, "bloom"],
         ["BF.INFO", bloom_info_command, "readonly fast", 1, 1, 1, "bloom"],
         ["BF.INSERT", bloom_insert_command, "write fast deny-oom", 1, 1, 1, "bloom"],
-        ["BF.LOAD", bloom_load_command, "write fast deny-oom", 1, 1, 1, "bloom"]
+        ["BF.LOAD", bloom_load_command, "write fast deny-oom", 1, 1, 1, "bloom"],
+        ["BF.MERGE", bloom_merge_command, "write deny-oom", 1, -1, 1, "bloom"],
+        ["BF.SCANDUMP", bloom_scandump_command, "readonly", 1, 1, 1, "bloom"],
+        ["BF.LOADCHUNK", bloom_loadchunk_command, "write deny-oom", 1, 1, 1, "bloom"],
+        ["BF.EXPORT", bloom_export_command, "readonly", 1, 1, 1, "bloom"],
+        ["BF.IMPORT", bloom_import_command, "write deny-oom", 1, 1, 1, "bloom"],
+        ["BF.DEL", bloom_del_command, "write fast deny-oom", 1, 1, 1, "bloom"],
+        ["BF.CASCADE.BUILD", bloom_cascade_build_command, "write deny-oom", 1, 1, 1, "bloom"],
+        ["BF.CASCADE.QUERY", bloom_cascade_query_command, "readonly fast", 1, 1, 1, "bloom"],
+        ["BF.CASCADE.INFO", bloom_cascade_info_command, "readonly fast", 1, 1, 1, "bloom"],
+        ["BF.CASCADE.LOAD", bloom_cascade_load_command, "write deny-oom", 1, 1, 1, "bloom"]
     ],
     configurations: [
         i64: [
             ["bloom-capacity", &*configs::BLOOM_CAPACITY, configs::BLOOM_CAPACITY_DEFAULT, configs::BLOOM_CAPACITY_MIN, configs::BLOOM_CAPACITY_MAX, ConfigurationFlags::DEFAULT, None],
             ["bloom-expansion", &*configs::BLOOM_EXPANSION, configs::BLOOM_EXPANSION_DEFAULT, configs::BLOOM_EXPANSION_MIN as i64, configs::BLOOM_EXPANSION_MAX as i64, ConfigurationFlags::DEFAULT, None],
             ["bloom-memory-limit-per-filter", &*configs::BLOOM_MEMORY_LIMIT_PER_FILTER, configs::BLOOM_MEMORY_LIMIT_PER_FILTER_DEFAULT, configs::BLOOM_MEMORY_LIMIT_PER_FILTER_MIN, configs::BLOOM_MEMORY_LIMIT_PER_FILTER_MAX, ConfigurationFlags::DEFAULT, None],
+            ["bloom-total-memory-limit", &*configs::BLOOM_TOTAL_MEMORY_LIMIT, configs::BLOOM_TOTAL_MEMORY_LIMIT_DEFAULT, configs::BLOOM_TOTAL_MEMORY_LIMIT_MIN, configs::BLOOM_TOTAL_MEMORY_LIMIT_MAX, ConfigurationFlags::DEFAULT, None],
+            ["bloom-bulk-parallel-threshold", &*configs::BLOOM_BULK_PARALLEL_THRESHOLD, configs::BLOOM_BULK_PARALLEL_THRESHOLD_DEFAULT, configs::BLOOM_BULK_PARALLEL_THRESHOLD_MIN, configs::BLOOM_BULK_PARALLEL_THRESHOLD_MAX, ConfigurationFlags::DEFAULT, None],
+            ["bloom-defrag-util-threshold", &*configs::BLOOM_DEFRAG_UTIL_THRESHOLD, configs::BLOOM_DEFRAG_UTIL_THRESHOLD_DEFAULT, configs::BLOOM_DEFRAG_UTIL_THRESHOLD_MIN, configs::BLOOM_DEFRAG_UTIL_THRESHOLD_MAX, ConfigurationFlags::DEFAULT, None],
         ],
         string: [
             ["bloom-fp-rate", &*configs::BLOOM_FP_RATE, configs::BLOOM_FP_RATE_DEFAULT, ConfigurationFlags::DEFAULT, None, Some(Box::new(configs::on_string_config_set))],
             ["bloom-tightening-ratio", &*configs::BLOOM_TIGHTENING_RATIO, configs::TIGHTENING_RATIO_DEFAULT, ConfigurationFlags::DEFAULT, None, Some(Box::new(configs::on_string_config_set))],
+            ["bloom-bitmap-compression", &*configs::BLOOM_BITMAP_COMPRESSION, configs::BLOOM_BITMAP_COMPRESSION_DEFAULT, ConfigurationFlags::DEFAULT, None, Some(Box::new(configs::on_string_config_set))],
+            ["bloom-filter-algorithm", &*configs::BLOOM_FILTER_ALGORITHM, configs::BLOOM_FILTER_ALGORITHM_DEFAULT, ConfigurationFlags::DEFAULT, None, Some(Box::new(configs::on_string_config_set))],
         ],
         bool: [
             ["bloom-use-random-seed", &*configs::BLOOM_USE_RANDOM_SEED, configs::BLOOM_USE_RANDOM_SEED_DEFAULT, ConfigurationFlags::DEFAULT, None],
             ["bloom-defrag-enabled", &*configs::BLOOM_DEFRAG, configs::BLOOM_DEFRAG_DEAFULT,  ConfigurationFlags::DEFAULT, None],
+            ["bloom-optimize-for-memory", &*configs::BLOOM_OPTIMIZE_FOR_MEMORY, configs::BLOOM_OPTIMIZE_FOR_MEMORY_DEFAULT, ConfigurationFlags::DEFAULT, None],
         ],
         enum: [
         ],
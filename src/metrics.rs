@@ -1,3 +1,5 @@
+use crate::bloom::data_type;
+use crate::configs;
 use lazy_static::lazy_static;
 use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use valkey_module::{InfoContext, ValkeyResult};
@@ -10,6 +12,18 @@ lazy_static! {
     pub static ref BLOOM_CAPACITY_ACROSS_OBJECTS: AtomicU64 = AtomicU64::new(0);
     pub static ref BLOOM_DEFRAG_HITS: AtomicU64 = AtomicU64::new(0);
     pub static ref BLOOM_DEFRAG_MISSES: AtomicU64 = AtomicU64::new(0);
+    // Allocations `Defrag::should_realloc` skipped because their `malloc_usable_size` utilization was
+    // already above `bloom-defrag-util-threshold`, counted separately from `BLOOM_DEFRAG_MISSES` (an
+    // attempted relocation that failed) so operators can tell "already well-placed" apart from "move
+    // failed".
+    pub static ref BLOOM_DEFRAG_SKIPPED_HEALTHY: AtomicU64 = AtomicU64::new(0);
+    // Number of `bloom_defrag` invocations that ran out of time budget before reaching the last
+    // sub-filter and had to save a cursor to resume from on a later call.
+    pub static ref BLOOM_DEFRAG_INCOMPLETE_PASSES: AtomicU64 = AtomicU64::new(0);
+    // Total bytes freed back to the allocator by successful relocations, measured as each
+    // `malloc_usable_size` delta between the old and new allocation. Lets operators see how much
+    // fragmentation defrag is actually recovering, as opposed to just how many allocations it touched.
+    pub static ref BLOOM_DEFRAG_BYTES_RECLAIMED: AtomicU64 = AtomicU64::new(0);
 }
 
 pub fn bloom_info_handler(ctx: &InfoContext) -> ValkeyResult<()> {
@@ -21,6 +35,14 @@ pub fn bloom_info_handler(ctx: &InfoContext) -> ValkeyResult<()> {
                 .load(Ordering::Relaxed)
                 .to_string(),
         )?
+        .field(
+            // 0 means the `bloom-total-memory-limit` budget is disabled; see
+            // `bloom::utils::BloomObject::validate_global_memory_budget`.
+            "bloom_total_memory_limit",
+            configs::BLOOM_TOTAL_MEMORY_LIMIT
+                .load(Ordering::Relaxed)
+                .to_string(),
+        )?
         .field(
             "bloom_num_objects",
             BLOOM_NUM_OBJECTS.load(Ordering::Relaxed).to_string(),
@@ -53,6 +75,38 @@ pub fn bloom_info_handler(ctx: &InfoContext) -> ValkeyResult<()> {
             "bloom_defrag_misses",
             BLOOM_DEFRAG_MISSES.load(Ordering::Relaxed).to_string(),
         )?
+        .field(
+            "bloom_defrag_skipped_healthy",
+            BLOOM_DEFRAG_SKIPPED_HEALTHY
+                .load(Ordering::Relaxed)
+                .to_string(),
+        )?
+        .field(
+            "bloom_defrag_incomplete_passes",
+            BLOOM_DEFRAG_INCOMPLETE_PASSES
+                .load(Ordering::Relaxed)
+                .to_string(),
+        )?
+        .field(
+            "bloom_defrag_bytes_reclaimed",
+            BLOOM_DEFRAG_BYTES_RECLAIMED
+                .load(Ordering::Relaxed)
+                .to_string(),
+        )?
+        .build_section()?
+        .add_section("bloom_rdb_compat")
+        .field(
+            // Whether the RDB/AOF most recently loaded (if any) was written by a module whose
+            // supported `bloomfltr` version range overlaps ours, i.e. whether downgrading to it would
+            // be safe. "unknown" until this process has loaded an RDB/AOF containing a bloomfltr key.
+            "bloom_rdb_compat_peer_overlaps",
+            match data_type::last_loaded_rdb_compat_manifest() {
+                Some(m) => (m.peer_min_encver <= data_type::BLOOM_FILTER_TYPE_ENCODING_VERSION
+                    && m.peer_max_encver >= data_type::BLOOM_FILTER_TYPE_MIN_ENCODING_VERSION)
+                    .to_string(),
+                None => "unknown".to_string(),
+            },
+        )?
         .build_section()?
         .build_info()?;
 
@@ -1,4 +1,6 @@
 use crate::bloom;
+use crate::bloom::cascade::BloomCascade;
+use crate::bloom::compression;
 use crate::bloom::data_type::ValkeyDataType;
 use crate::bloom::utils::BloomFilter;
 use crate::bloom::utils::BloomObject;
@@ -24,6 +26,13 @@ use super::defrag::Defrag;
 // The reason they are unsafe is because the callback methods are expected to be
 // "unsafe extern C" based on the Rust module API definition
 
+/// Writes a zero-length extension blob: the reserved, currently-unused trailing slot the version 2
+/// `bloomfltr` format leaves after each sub-filter and once more after the last one for a future version to
+/// add fields into. See `data_type::BLOOM_FILTER_TYPE_ENCODING_VERSION`.
+unsafe fn save_empty_extension_blob(rdb: *mut raw::RedisModuleIO) {
+    raw::RedisModule_SaveStringBuffer.unwrap()(rdb, [].as_ptr().cast::<c_char>(), 0);
+}
+
 /// # Safety
 pub unsafe extern "C" fn bloom_rdb_save(rdb: *mut raw::RedisModuleIO, value: *mut c_void) {
     let v = &*value.cast::<BloomObject>();
@@ -38,16 +47,22 @@ pub unsafe extern "C" fn bloom_rdb_save(rdb: *mut raw::RedisModuleIO, value: *mu
     while let Some(filter) = filter_list_iter.next() {
         let bloom = filter.raw_bloom();
         let bitmap = bloom.as_slice();
+        // Compressed with the same `bloom-bitmap-compression` codec `BloomObject::encode_object` uses for
+        // AOF/`BF.LOAD`, so RDB saves of sparsely-populated filters don't pay to write mostly-zero bytes
+        // verbatim. See `BLOOM_FILTER_TYPE_ENCODING_VERSION` / `load_from_rdb_v3`.
+        let compressed_bitmap = compression::compress(bitmap, configs::bitmap_compression_codec());
         raw::RedisModule_SaveStringBuffer.unwrap()(
             rdb,
-            bitmap.as_ptr().cast::<c_char>(),
-            bitmap.len(),
+            compressed_bitmap.as_ptr().cast::<c_char>(),
+            compressed_bitmap.len(),
         );
         raw::save_unsigned(rdb, filter.capacity() as u64);
         if filter_list_iter.peek().is_none() {
             raw::save_unsigned(rdb, filter.num_items() as u64);
         }
+        save_empty_extension_blob(rdb);
     }
+    save_empty_extension_blob(rdb);
 }
 
 /// # Safety
@@ -100,6 +115,14 @@ pub unsafe extern "C" fn bloom_aux_load(
     bloom::data_type::bloom_rdb_aux_load(rdb)
 }
 
+/// # Safety
+/// Save the bloomfltr aux (out of keyspace) compatibility manifest to RDB. Registered for both
+/// `aux_save` and `aux_save2` - they share the same callback signature and we have no need to
+/// distinguish them.
+pub unsafe extern "C" fn bloom_aux_save(rdb: *mut raw::RedisModuleIO, _when: c_int) {
+    bloom::data_type::bloom_rdb_aux_save(rdb)
+}
+
 /// # Safety
 /// Free a bloom object
 pub unsafe extern "C" fn bloom_free(value: *mut c_void) {
@@ -144,10 +167,118 @@ pub unsafe extern "C" fn bloom_free_effort(
     curr_item.free_effort()
 }
 
-// Lazy static for a default temporary external crate Bloom structure that gets swapped during defrag.
+/// # Safety
+/// Raw handler for the `BloomCascade` RDB save callback. Unlike `bloom_rdb_save`, a cascade is saved as a
+/// single length-prefixed blob (`BloomCascade::encode_cascade`) rather than chunked per sub-filter, since
+/// cascades are built once and not incrementally scaled.
+pub unsafe extern "C" fn cascade_rdb_save(rdb: *mut raw::RedisModuleIO, value: *mut c_void) {
+    let cascade = &*value.cast::<BloomCascade>();
+    match cascade.encode_cascade() {
+        Ok(bytes) => {
+            raw::RedisModule_SaveStringBuffer.unwrap()(
+                rdb,
+                bytes.as_ptr().cast::<c_char>(),
+                bytes.len(),
+            );
+        }
+        Err(err) => {
+            logging::log_warning(
+                format!("Failed to save bloom cascade to RDB: {}", err.as_str()).as_str(),
+            );
+        }
+    }
+}
+
+/// # Safety
+pub unsafe extern "C" fn cascade_rdb_load(
+    rdb: *mut raw::RedisModuleIO,
+    _encver: c_int,
+) -> *mut c_void {
+    let Ok(bytes) = raw::load_string_buffer(rdb) else {
+        logging::log_warning("Failed to restore bloom cascade.");
+        return null_mut();
+    };
+    match BloomCascade::decode_cascade(bytes.as_ref()) {
+        Ok(cascade) => Box::into_raw(Box::new(cascade)).cast::<libc::c_void>(),
+        Err(err) => {
+            logging::log_warning(
+                format!("Failed to restore bloom cascade: {}", err.as_str()).as_str(),
+            );
+            null_mut()
+        }
+    }
+}
+
+/// # Safety
+/// Mirrors `bloom_aof_rewrite`'s pattern for `BloomObject`: emits a `BF.CASCADE.LOAD <key> <data>` that
+/// reconstructs the cascade in full on AOF replay. Required so a cascade key persists when
+/// `aof-use-rdb-preamble no` is set - without it, a cascade would silently vanish from a rewritten AOF.
+pub unsafe extern "C" fn cascade_aof_rewrite(
+    aof: *mut raw::RedisModuleIO,
+    key: *mut raw::RedisModuleString,
+    value: *mut c_void,
+) {
+    let cascade = &*value.cast::<BloomCascade>();
+    let bytes = match cascade.encode_cascade() {
+        Ok(val) => val,
+        Err(err) => {
+            log_io_error(aof, ValkeyLogLevel::Warning, err.as_str());
+            return;
+        }
+    };
+    let cmd = CString::new("BF.CASCADE.LOAD").unwrap();
+    let fmt = CString::new("sb").unwrap();
+    valkey_module::raw::RedisModule_EmitAOF.unwrap()(
+        aof,
+        cmd.as_ptr(),
+        fmt.as_ptr(),
+        key,
+        bytes.as_ptr().cast::<c_char>(),
+        bytes.len(),
+    );
+}
+
+/// # Safety
+/// Free a bloom cascade.
+pub unsafe extern "C" fn cascade_free(value: *mut c_void) {
+    drop(Box::from_raw(value.cast::<BloomCascade>()));
+}
+
+/// # Safety
+/// Compute the memory usage for a bloom cascade.
+pub unsafe extern "C" fn cascade_mem_usage(value: *const c_void) -> usize {
+    let cascade = &*value.cast::<BloomCascade>();
+    cascade.memory_usage()
+}
+
+/// # Safety
+/// Raw handler for the COPY command on a bloom cascade. Cascades are immutable once built, so COPY just
+/// round-trips through the same encode/decode used for RDB.
+pub unsafe extern "C" fn cascade_copy(
+    _from_key: *mut RedisModuleString,
+    _to_key: *mut RedisModuleString,
+    value: *const c_void,
+) -> *mut c_void {
+    let curr_item = &*value.cast::<BloomCascade>();
+    match curr_item
+        .encode_cascade()
+        .and_then(|bytes| BloomCascade::decode_cascade(&bytes))
+    {
+        Ok(copy) => Box::into_raw(Box::new(copy)).cast::<libc::c_void>(),
+        Err(_) => null_mut(),
+    }
+}
+
+// Lazy statics for default temporary placeholders that get swapped in during defrag while the real
+// value they stand in for is being relocated.
 lazy_static! {
     static ref DEFRAG_BLOOM_FILTER: Mutex<Option<Box<Bloom<[u8]>>>> =
         Mutex::new(Some(Box::new(Bloom::<[u8]>::new(1, 1).unwrap())));
+    // Stands in for a `BloomObject`'s sub-filter slot in `bloom_defrag` so each filter can be swapped out
+    // and back in with `mem::replace` (an O(1) in-place swap) instead of `Vec::remove`/`Vec::insert`,
+    // which shift every element after `cursor` and turn one defrag pass into O(n^2) in sub-filter count.
+    static ref DEFRAG_FILTER_PLACEHOLDER: Mutex<Option<Box<BloomFilter>>> =
+        Mutex::new(Some(Box::new(BloomFilter::with_fixed_seed(0.01, 1, &[0u8; 32]))));
 }
 
 /// Defragments a vector of bytes (bit vector) of the external crate Bloom structure. This function is designed to be
@@ -170,9 +301,17 @@ fn external_vec_defrag(vec: Vec<u8>) -> Vec<u8> {
     let len = vec.len();
     let capacity = vec.capacity();
     let vec_ptr = Box::into_raw(vec.into_boxed_slice()) as *mut c_void;
+    if !unsafe { defrag.should_realloc(vec_ptr, len) } {
+        metrics::BLOOM_DEFRAG_SKIPPED_HEALTHY.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        return unsafe { Vec::from_raw_parts(vec_ptr as *mut u8, len, capacity) };
+    }
+    let old_usable = unsafe { libc::malloc_usable_size(vec_ptr) };
     let defragged_filters_ptr = unsafe { defrag.alloc(vec_ptr) };
     if !defragged_filters_ptr.is_null() {
         metrics::BLOOM_DEFRAG_HITS.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        record_bytes_reclaimed(old_usable, unsafe {
+            libc::malloc_usable_size(defragged_filters_ptr)
+        });
         unsafe { Vec::from_raw_parts(defragged_filters_ptr as *mut u8, len, capacity) }
     } else {
         metrics::BLOOM_DEFRAG_MISSES.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
@@ -180,6 +319,16 @@ fn external_vec_defrag(vec: Vec<u8>) -> Vec<u8> {
     }
 }
 
+/// Adds the usable-size delta between an allocation's pre- and post-relocation `malloc_usable_size` to
+/// `BLOOM_DEFRAG_BYTES_RECLAIMED`. Saturates to `0` instead of wrapping if a relocated allocation happens
+/// to report a larger usable size than the one it replaced.
+fn record_bytes_reclaimed(old_usable: usize, new_usable: usize) {
+    metrics::BLOOM_DEFRAG_BYTES_RECLAIMED.fetch_add(
+        old_usable.saturating_sub(new_usable) as u64,
+        std::sync::atomic::Ordering::Relaxed,
+    );
+}
+
 /// # Safety
 /// Raw handler for the Bloom object's defrag callback.
 ///
@@ -234,13 +383,27 @@ pub unsafe extern "C" fn bloom_defrag(
 
     // While we are within a timeframe decided from should_stop_defrag and not over the number of filters defrag the next filter
     while !defrag.should_stop_defrag() && cursor < num_filters as u64 {
-        // Remove the current BloomFilter, unbox it, and attempt to defragment the BloomFilter.
-        let bloom_filter_box = bloom_object.filters_mut().remove(cursor as usize);
+        // Swap the current BloomFilter out for the shared placeholder (an O(1) in-place swap, unlike
+        // `Vec::remove` which would shift every following element), unbox it, and attempt to defragment it.
+        let mut temporary_filter = DEFRAG_FILTER_PLACEHOLDER
+            .lock()
+            .expect("We expect default to exist");
+        let bloom_filter_box = mem::replace(
+            &mut bloom_object.filters_mut()[cursor as usize],
+            temporary_filter.take().expect("We expect default to exist"),
+        );
         let bloom_filter = Box::into_raw(bloom_filter_box);
-        let defrag_result = defrag.alloc(bloom_filter as *mut c_void);
-        let mut defragged_filter = {
+        let mut defragged_filter = if !defrag
+            .should_realloc(bloom_filter as *mut c_void, mem::size_of::<BloomFilter>())
+        {
+            metrics::BLOOM_DEFRAG_SKIPPED_HEALTHY.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            Box::from_raw(bloom_filter)
+        } else {
+            let old_usable = libc::malloc_usable_size(bloom_filter as *mut c_void);
+            let defrag_result = defrag.alloc(bloom_filter as *mut c_void);
             if !defrag_result.is_null() {
                 metrics::BLOOM_DEFRAG_HITS.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                record_bytes_reclaimed(old_usable, libc::malloc_usable_size(defrag_result));
                 Box::from_raw(defrag_result as *mut BloomFilter)
             } else {
                 metrics::BLOOM_DEFRAG_MISSES.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
@@ -257,56 +420,70 @@ pub unsafe extern "C" fn bloom_defrag(
         );
         // Convert the inner_bloom into the correct type and then try to defragment it
         let inner_bloom_ptr = Box::into_raw(inner_bloom);
-        let defragged_inner_bloom = defrag.alloc(inner_bloom_ptr as *mut c_void);
         // Defragment the Bit Vec within the external crate Bloom structure using the external callback
-        if !defragged_inner_bloom.is_null() {
-            metrics::BLOOM_DEFRAG_HITS.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        if !defrag.should_realloc(
+            inner_bloom_ptr as *mut c_void,
+            mem::size_of::<bloomfilter::Bloom<[u8]>>(),
+        ) {
+            metrics::BLOOM_DEFRAG_SKIPPED_HEALTHY.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
 
-            let inner_bloom =
-                unsafe { Box::from_raw(defragged_inner_bloom as *mut bloomfilter::Bloom<[u8]>) };
+            let inner_bloom = unsafe { Box::from_raw(inner_bloom_ptr) };
             let external_bloom =
                 inner_bloom.realloc_large_heap_allocated_objects(external_vec_defrag);
             let placeholder_bloom =
                 mem::replace(defragged_filter.raw_bloom_mut(), Box::new(external_bloom));
             *temporary_bloom = Some(placeholder_bloom); // Reset the original static
         } else {
-            metrics::BLOOM_DEFRAG_MISSES.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            let old_usable = libc::malloc_usable_size(inner_bloom_ptr as *mut c_void);
+            let defragged_inner_bloom = defrag.alloc(inner_bloom_ptr as *mut c_void);
+            if !defragged_inner_bloom.is_null() {
+                metrics::BLOOM_DEFRAG_HITS.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                record_bytes_reclaimed(old_usable, libc::malloc_usable_size(defragged_inner_bloom));
 
-            let inner_bloom = unsafe { Box::from_raw(inner_bloom_ptr) };
-            let external_bloom =
-                inner_bloom.realloc_large_heap_allocated_objects(external_vec_defrag);
-            let placeholder_bloom =
-                mem::replace(defragged_filter.raw_bloom_mut(), Box::new(external_bloom));
-            *temporary_bloom = Some(placeholder_bloom); // Reset the original static
+                let inner_bloom = unsafe {
+                    Box::from_raw(defragged_inner_bloom as *mut bloomfilter::Bloom<[u8]>)
+                };
+                let external_bloom =
+                    inner_bloom.realloc_large_heap_allocated_objects(external_vec_defrag);
+                let placeholder_bloom =
+                    mem::replace(defragged_filter.raw_bloom_mut(), Box::new(external_bloom));
+                *temporary_bloom = Some(placeholder_bloom); // Reset the original static
+            } else {
+                metrics::BLOOM_DEFRAG_MISSES.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+
+                let inner_bloom = unsafe { Box::from_raw(inner_bloom_ptr) };
+                let external_bloom =
+                    inner_bloom.realloc_large_heap_allocated_objects(external_vec_defrag);
+                let placeholder_bloom =
+                    mem::replace(defragged_filter.raw_bloom_mut(), Box::new(external_bloom));
+                *temporary_bloom = Some(placeholder_bloom); // Reset the original static
+            }
         }
 
-        // Reinsert the defragmented filter and increment the cursor
-        bloom_object
-            .filters_mut()
-            .insert(cursor as usize, defragged_filter);
+        // Swap the defragmented filter back into its slot and return the placeholder to the static for
+        // the next iteration, then increment the cursor.
+        let placeholder_filter = mem::replace(
+            &mut bloom_object.filters_mut()[cursor as usize],
+            defragged_filter,
+        );
+        *temporary_filter = Some(placeholder_filter);
         cursor += 1;
     }
     // Save the cursor for where we will start defragmenting from next time
     defrag.set_cursor(cursor);
     // If not all filters were looked at, return 1 to indicate incomplete defragmentation
     if cursor < num_filters as u64 {
+        metrics::BLOOM_DEFRAG_INCOMPLETE_PASSES.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
         return 1;
     }
     // Defragment the Vec of BloomFilter/s itself
     let filters_vec = mem::take(bloom_object.filters_mut());
     let filters_ptr = Box::into_raw(filters_vec.into_boxed_slice()) as *mut c_void;
-    let defragged_filters_ptr = defrag.alloc(filters_ptr);
-    if !defragged_filters_ptr.is_null() {
-        metrics::BLOOM_DEFRAG_HITS.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-        *bloom_object.filters_mut() = unsafe {
-            Vec::from_raw_parts(
-                defragged_filters_ptr as *mut Box<BloomFilter>,
-                num_filters,
-                filters_capacity,
-            )
-        };
-    } else {
-        metrics::BLOOM_DEFRAG_HITS.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    if !defrag.should_realloc(
+        filters_ptr,
+        num_filters * mem::size_of::<Box<BloomFilter>>(),
+    ) {
+        metrics::BLOOM_DEFRAG_SKIPPED_HEALTHY.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
         *bloom_object.filters_mut() = unsafe {
             Vec::from_raw_parts(
                 filters_ptr as *mut Box<BloomFilter>,
@@ -314,15 +491,97 @@ pub unsafe extern "C" fn bloom_defrag(
                 filters_capacity,
             )
         };
+    } else {
+        let old_usable = libc::malloc_usable_size(filters_ptr);
+        let defragged_filters_ptr = defrag.alloc(filters_ptr);
+        if !defragged_filters_ptr.is_null() {
+            metrics::BLOOM_DEFRAG_HITS.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            record_bytes_reclaimed(old_usable, libc::malloc_usable_size(defragged_filters_ptr));
+            *bloom_object.filters_mut() = unsafe {
+                Vec::from_raw_parts(
+                    defragged_filters_ptr as *mut Box<BloomFilter>,
+                    num_filters,
+                    filters_capacity,
+                )
+            };
+        } else {
+            metrics::BLOOM_DEFRAG_MISSES.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            *bloom_object.filters_mut() = unsafe {
+                Vec::from_raw_parts(
+                    filters_ptr as *mut Box<BloomFilter>,
+                    num_filters,
+                    filters_capacity,
+                )
+            };
+        }
     }
     // Finally, attempt to defragment the BloomObject itself
-    let val = defrag.alloc(*value);
-    if !val.is_null() {
-        metrics::BLOOM_DEFRAG_HITS.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-        *value = val;
+    if !defrag.should_realloc(*value, mem::size_of::<BloomObject>()) {
+        metrics::BLOOM_DEFRAG_SKIPPED_HEALTHY.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
     } else {
-        metrics::BLOOM_DEFRAG_MISSES.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        let old_usable = libc::malloc_usable_size(*value);
+        let val = defrag.alloc(*value);
+        if !val.is_null() {
+            metrics::BLOOM_DEFRAG_HITS.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            record_bytes_reclaimed(old_usable, libc::malloc_usable_size(val));
+            *value = val;
+        } else {
+            metrics::BLOOM_DEFRAG_MISSES.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        }
     }
     // Return 0 to indicate successful complete defragmentation
     0
 }
+
+/// # Safety
+/// Global defrag callback, registered alongside the per-key `bloom_defrag` callback. The engine calls
+/// this once per defrag cycle to give the module a chance to defragment state that isn't owned by any
+/// single key - here, the `DEFRAG_BLOOM_FILTER` placeholder that `bloom_defrag` swaps in and out while it
+/// defragments each key's inner `Bloom` structure.
+///
+/// We only act when the mutex is uncontended: if `bloom_defrag` (or another invocation of this callback)
+/// currently holds it, the placeholder is mid-swap and isn't ours to move, so we skip this cycle rather
+/// than block the defrag thread.
+///
+/// # Arguments
+///
+/// * `defrag_ctx` - A raw pointer to the defragmentation context.
+///
+/// # Returns
+///
+/// Always returns 0: there is exactly one global placeholder to defragment, so this callback never has
+/// incomplete work to resume via a cursor.
+pub unsafe extern "C" fn bloom_defrag_global(defrag_ctx: *mut RedisModuleDefragCtx) -> i32 {
+    if !configs::BLOOM_DEFRAG.load(Ordering::Relaxed) {
+        return 0;
+    }
+
+    let defrag = Defrag::new(defrag_ctx);
+    let Ok(mut temporary_bloom) = DEFRAG_BLOOM_FILTER.try_lock() else {
+        return 0;
+    };
+    let Some(placeholder_bloom) = temporary_bloom.take() else {
+        return 0;
+    };
+
+    let placeholder_ptr = Box::into_raw(placeholder_bloom);
+    let relocated_bloom = if !defrag.should_realloc(
+        placeholder_ptr as *mut c_void,
+        mem::size_of::<Bloom<[u8]>>(),
+    ) {
+        metrics::BLOOM_DEFRAG_SKIPPED_HEALTHY.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        Box::from_raw(placeholder_ptr)
+    } else {
+        let defrag_result = defrag.alloc(placeholder_ptr as *mut c_void);
+        if !defrag_result.is_null() {
+            metrics::BLOOM_DEFRAG_HITS.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            Box::from_raw(defrag_result as *mut Bloom<[u8]>)
+        } else {
+            metrics::BLOOM_DEFRAG_MISSES.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            Box::from_raw(placeholder_ptr)
+        }
+    };
+    let relocated_bloom = relocated_bloom.realloc_large_heap_allocated_objects(external_vec_defrag);
+    *temporary_bloom = Some(Box::new(relocated_bloom));
+    0
+}
@@ -1,6 +1,10 @@
 use std::os::raw::c_void;
+use std::sync::atomic::Ordering;
 
 use valkey_module::{raw, Status};
+
+use crate::configs;
+
 pub struct Defrag {
     pub defrag_ctx: *mut raw::RedisModuleDefragCtx,
 }
@@ -17,6 +21,33 @@ impl Defrag {
         unsafe { raw::RedisModule_DefragAlloc.unwrap()(self.defrag_ctx, ptr) }
     }
 
+    /// Returns whether `ptr` - a live allocation whose logical size is `requested_bytes` - is worth
+    /// handing to `alloc`. jemalloc doesn't expose a stable, public API for a single allocation's
+    /// per-run utilization, so we fall back to the same signal the rest of this module already uses for
+    /// byte accounting (`malloc_usable_size`, see `bloom::utils::usable_bitmap_bytes` and
+    /// `record_bytes_reclaimed`): an allocation whose usable size is far larger than what it actually
+    /// needs is sitting in a size class with slack defrag could reclaim, so we compare `requested_bytes`
+    /// against `malloc_usable_size(ptr)` as a percentage and relocate when that utilization dips below
+    /// `bloom-defrag-util-threshold`, mirroring Valkey's own `active-defrag-threshold-lower` semantics.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be a live allocation obtained from the allocator jemalloc is configured as.
+    pub unsafe fn should_realloc(&self, ptr: *mut c_void, requested_bytes: usize) -> bool {
+        if requested_bytes == 0 {
+            return false;
+        }
+        let usable = unsafe { libc::malloc_usable_size(ptr) };
+        if usable <= requested_bytes {
+            return false;
+        }
+        let threshold = configs::BLOOM_DEFRAG_UTIL_THRESHOLD
+            .load(Ordering::Relaxed)
+            .clamp(0, 100) as u64;
+        let utilization_pct = (requested_bytes as u64) * 100 / usable as u64;
+        utilization_pct < threshold
+    }
+
     /// # Safety
     ///
     /// This function is temporary and will be removed once implemented in valkeymodule-rs .